@@ -0,0 +1,236 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::cid::normalize_cid;
+use crate::error::{ensure, SourceScanError};
+use crate::events::{AdvisoryData, Event};
+use crate::{AdminLogAction, SourceScan};
+
+/// What an advisory applies to. Code hash advisories cover every account running that
+/// code, including factory children.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum AdvisoryTarget {
+    Account(AccountId),
+    CodeHash(String),
+}
+
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Advisory {
+    pub target: AdvisoryTarget,
+    pub severity: Severity,
+    /// IPFS CID of the write-up, in canonical form.
+    pub description_cid: String,
+    pub published_by: AccountId,
+    pub published_at: U64,
+    /// Cleared by `withdraw_advisory` once the issue is fixed or found invalid.
+    pub active: bool,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Publishes a known vulnerability, returning the advisory id.
+    #[handle_result]
+    pub fn publish_advisory(
+        &mut self,
+        target: AdvisoryTarget,
+        severity: Severity,
+        description_cid: String,
+    ) -> Result<u64, SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        let target = match target {
+            AdvisoryTarget::CodeHash(code_hash) => {
+                AdvisoryTarget::CodeHash(self.check_valid_code_hash(&code_hash)?)
+            }
+            target => target,
+        };
+        let description_cid = normalize_cid(&description_cid)
+            .ok_or_else(|| SourceScanError::invalid_cid(&description_cid))?;
+
+        let advisory_id = self.advisories.len();
+        self.advisories.push(&Advisory {
+            target: target.clone(),
+            severity,
+            description_cid,
+            published_by: env::predecessor_account_id(),
+            published_at: U64(env::block_timestamp()),
+            active: true,
+        });
+        self.active_advisories.insert(&advisory_id);
+
+        let mut ids = self.advisory_index.get(&target).unwrap_or_default();
+        ids.push(advisory_id);
+        self.advisory_index.insert(&target, &ids);
+
+        self.log_admin(AdminLogAction::PublishAdvisory {
+            advisory_id,
+            target: target.clone(),
+            severity,
+        });
+        Event::AdvisoryPublished(vec![AdvisoryData {
+            advisory_id,
+            target,
+        }])
+        .emit();
+
+        Ok(advisory_id)
+    }
+
+    #[handle_result]
+    pub fn withdraw_advisory(&mut self, advisory_id: u64) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        let mut advisory = self
+            .advisories
+            .get(advisory_id)
+            .ok_or_else(|| SourceScanError::NotFound("Advisory not found".to_string()))?;
+        ensure!(
+            advisory.active,
+            SourceScanError::InvalidState("Advisory is already withdrawn".to_string())
+        );
+
+        advisory.active = false;
+        self.advisories.replace(advisory_id, &advisory);
+        self.active_advisories.remove(&advisory_id);
+        self.log_admin(AdminLogAction::WithdrawAdvisory { advisory_id });
+
+        Event::AdvisoryWithdrawn(vec![AdvisoryData {
+            advisory_id,
+            target: advisory.target,
+        }])
+        .emit();
+        Ok(())
+    }
+
+    /// Advisories for `account_id` and for the code hash of its verified entry, active
+    /// or not, oldest first.
+    pub fn get_advisories(&self, account_id: AccountId) -> Vec<(u64, Advisory)> {
+        let mut ids = self
+            .advisory_index
+            .get(&AdvisoryTarget::Account(account_id.clone()))
+            .unwrap_or_default();
+        if let Some(contract) = self.read_contract(&account_id) {
+            ids.extend(
+                self.advisory_index
+                    .get(&AdvisoryTarget::CodeHash(contract.code_hash))
+                    .unwrap_or_default(),
+            );
+        }
+        ids.sort_unstable();
+
+        ids.into_iter()
+            .filter_map(|id| Some((id, self.advisories.get(id)?)))
+            .collect()
+    }
+
+    pub fn get_active_advisories(&self, from_index: usize, limit: usize) -> Vec<(u64, Advisory)> {
+        self.active_advisories
+            .iter()
+            .skip(from_index)
+            .take(self.page_limit(limit))
+            .filter_map(|id| Some((id, self.advisories.get(id)?)))
+            .collect()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, code_hash, get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn with_entry() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        contract
+    }
+
+    #[test]
+    fn advisories_cover_account_and_code_hash() {
+        let mut contract = with_entry();
+        let by_account = contract
+            .publish_advisory(
+                AdvisoryTarget::Account(accounts(1)),
+                Severity::High,
+                cid("advisory1"),
+            )
+            .unwrap();
+        let by_code = contract
+            .publish_advisory(
+                AdvisoryTarget::CodeHash(code_hash("code_hash")),
+                Severity::Critical,
+                cid("advisory2"),
+            )
+            .unwrap();
+        contract
+            .publish_advisory(
+                AdvisoryTarget::Account(accounts(2)),
+                Severity::Low,
+                cid("advisory3"),
+            )
+            .unwrap();
+
+        let advisories = contract.get_advisories(accounts(1));
+        assert_eq!(
+            advisories.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![by_account, by_code]
+        );
+        assert_eq!(advisories[1].1.severity, Severity::Critical);
+        assert_eq!(contract.get_active_advisories(0, 10).len(), 3);
+
+        contract.add_verifier(accounts(2)).unwrap();
+        contract.pause().unwrap();
+        testing_env!(get_context(accounts(2)).build());
+        assert_eq!(
+            contract.withdraw_advisory(by_code),
+            Err(SourceScanError::Paused)
+        );
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.unpause().unwrap();
+        testing_env!(get_context(accounts(2)).build());
+        contract.withdraw_advisory(by_code).unwrap();
+        assert!(!contract.get_advisories(accounts(1))[1].1.active);
+        assert_eq!(contract.get_active_advisories(0, 10).len(), 2);
+    }
+
+    #[test]
+    fn others_cannot_publish() {
+        let mut contract = with_entry();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.publish_advisory(
+                AdvisoryTarget::Account(accounts(1)),
+                Severity::High,
+                cid("advisory"),
+            ),
+            Err(SourceScanError::Unauthorized(
+                "Only owner or verifier can call this method".to_string()
+            ))
+        );
+    }
+}
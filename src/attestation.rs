@@ -0,0 +1,472 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId, CurveType, PublicKey};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{AttestedData, Event};
+use crate::{AdminLogAction, SourceScan, VerificationStatus};
+
+/// A verifier's ed25519 signature over an entry, checkable off-chain with
+/// `attestation_message` and `public_key`.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Attestation {
+    pub signer: AccountId,
+    pub public_key: PublicKey,
+    /// Base58-encoded 64-byte signature.
+    pub signature: String,
+}
+
+/// How many independent verifiers vouch for the current code of an entry.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationLevel {
+    Single,
+    /// At least `Config::multi_attestation_threshold` verifiers attested.
+    Multi,
+}
+
+/// The signed message: `<account_id>:<code_hash>:<cid>`, with the code hash and CID in
+/// the canonical forms `get_contract` returns.
+pub(crate) fn attestation_message(account_id: &AccountId, code_hash: &str, cid: &str) -> String {
+    format!("{}:{}:{}", account_id, code_hash, cid)
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Registers the ed25519 key the calling verifier signs its verifications with.
+    #[handle_result]
+    pub fn set_attestation_key(&mut self, public_key: PublicKey) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        ensure!(
+            public_key.curve_type() == CurveType::ED25519,
+            SourceScanError::InvalidInput("Attestation keys must be ed25519".to_string())
+        );
+
+        self.attestation_keys
+            .insert(&env::predecessor_account_id(), &public_key);
+        self.log_admin(AdminLogAction::SetAttestationKey { public_key });
+        Ok(())
+    }
+
+    pub fn get_attestation_key(&self, account_id: AccountId) -> Option<PublicKey> {
+        self.attestation_keys.get(&account_id)
+    }
+
+    /// Adds the caller as an independent attester of the entry. `code_hash` must match
+    /// the entry so an attestation never lands on code the caller did not check. The
+    /// attestation that verifies a `Pending` entry mints its badge, so attach the badge
+    /// deposit then, and collects its bounty.
+    #[payable]
+    #[handle_result]
+    pub fn attest_contract(
+        &mut self,
+        account_id: AccountId,
+        code_hash: String,
+    ) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_staked()?;
+        self.check_not_paused()?;
+        let code_hash = self.check_valid_code_hash(&code_hash)?;
+
+        let contract = self
+            .read_contract(&account_id)
+            .ok_or_else(|| SourceScanError::no_contract(&account_id))?;
+        ensure!(
+            contract.code_hash == code_hash,
+            SourceScanError::InvalidInput(format!(
+                "Entry for {} has a different code hash",
+                account_id
+            ))
+        );
+        ensure!(
+            matches!(
+                contract.status,
+                VerificationStatus::Pending | VerificationStatus::Verified
+            ),
+            SourceScanError::InvalidState(
+                "Only pending or verified entries can be attested".to_string()
+            )
+        );
+
+        let attester = env::predecessor_account_id();
+        let mut attesters = self.attesters.get(&account_id).unwrap_or_default();
+        ensure!(
+            !attesters.contains(&attester),
+            SourceScanError::AlreadyExists(
+                "Entry is already attested by this verifier".to_string()
+            )
+        );
+        attesters.push(attester.clone());
+        let verifies = contract.status == VerificationStatus::Pending
+            && attesters.len() >= self.config.required_attestations as usize;
+        let mints_badge = verifies && self.mints_badge(&account_id, &code_hash);
+        self.charge_badge_deposit(mints_badge)?;
+
+        self.attesters.insert(&account_id, &attesters);
+        if verifies {
+            self.set_status(&account_id, VerificationStatus::Verified);
+            if mints_badge {
+                self.mint_badge(&account_id, &code_hash);
+            }
+            self.pay_bounty(&account_id);
+        }
+
+        Event::ContractAttested(vec![AttestedData {
+            account_id,
+            code_hash,
+            attester,
+        }])
+        .emit();
+        Ok(())
+    }
+
+    /// Verifiers that attested to the current code of `account_id`, first one first.
+    pub fn get_attesters(&self, account_id: AccountId) -> Vec<AccountId> {
+        self.attesters.get(&account_id).unwrap_or_default()
+    }
+
+    pub fn get_verification_level(&self, account_id: AccountId) -> Option<VerificationLevel> {
+        self.contracts.get(&account_id)?;
+
+        let attesters = self.attesters.get(&account_id).unwrap_or_default();
+        if attesters.len() >= self.config.multi_attestation_threshold as usize {
+            Some(VerificationLevel::Multi)
+        } else {
+            Some(VerificationLevel::Single)
+        }
+    }
+}
+
+impl SourceScan {
+    /// Status a verification by the caller stores: `Pending` until
+    /// `Config::required_attestations` verifiers, the caller included, attested the code.
+    pub(crate) fn attested_status(
+        &self,
+        account_id: &AccountId,
+        same_code: bool,
+    ) -> VerificationStatus {
+        let verifier = env::predecessor_account_id();
+        let attesters = if same_code {
+            self.attesters.get(account_id).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let count = attesters.len() + usize::from(!attesters.contains(&verifier));
+
+        if count >= self.config.required_attestations as usize {
+            VerificationStatus::Verified
+        } else {
+            VerificationStatus::Pending
+        }
+    }
+
+    /// Counts the caller of a verification as an attester. New code starts over from a
+    /// single attestation.
+    pub(crate) fn record_attester(&mut self, account_id: &AccountId, same_code: bool) {
+        let verifier = env::predecessor_account_id();
+        let mut attesters = if same_code {
+            self.attesters.get(account_id).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        if !attesters.contains(&verifier) {
+            attesters.push(verifier);
+        }
+        self.attesters.insert(account_id, &attesters);
+    }
+
+    /// Checks `signature` against the caller's registered key and returns the attestation
+    /// to store with the entry.
+    pub(crate) fn attest(
+        &self,
+        account_id: &AccountId,
+        code_hash: &str,
+        cid: &str,
+        signature: String,
+    ) -> Result<Attestation, SourceScanError> {
+        let signer = env::predecessor_account_id();
+        let public_key = self.attestation_keys.get(&signer).ok_or_else(|| {
+            SourceScanError::NotFound(
+                "Register an attestation key before signing verifications".to_string(),
+            )
+        })?;
+        let invalid =
+            || SourceScanError::InvalidSignature("Invalid attestation signature".to_string());
+
+        let signature_bytes: [u8; 64] = near_sdk::bs58::decode(&signature)
+            .into_vec()
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(invalid)?;
+        let key_bytes: &[u8; 32] = public_key.as_bytes()[1..].try_into().map_err(|_| {
+            SourceScanError::InvalidSignature("Invalid attestation key".to_string())
+        })?;
+        ensure!(
+            env::ed25519_verify(
+                &signature_bytes,
+                attestation_message(account_id, code_hash, cid).as_bytes(),
+                key_bytes,
+            ),
+            invalid()
+        );
+
+        Ok(Attestation {
+            signer,
+            public_key,
+            signature,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{code_hash, entry, get_context, set_entry};
+    use crate::{Config, SearchFilters, StatusFilter};
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, get_logs};
+    use near_sdk::{testing_env, NearToken};
+
+    const KEY: &str = "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp";
+    /// Public key of the first RFC 8032 ed25519 test vector.
+    const SIGNING_KEY: &str = "ed25519:FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z";
+    const SIGNED_CODE_HASH: &str = "FNB8GJZ2tzLRmUxvFhZmE8QHNT4ep7C2s7SRxTpeLmEq";
+    const SIGNED_CID: &str = "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34";
+    /// Signature over `bob:<SIGNED_CODE_HASH>:<SIGNED_CID>` with that vector's secret key.
+    const SIGNATURE: &str =
+        "3xjn96Lyn6Mt5F2JQzKiCBnL8ryazpHL5kAqcS69DGUry9WJDSMq7sXheFe5GyXCmCD6Z1mm6rNJMpvUThq27D74";
+
+    fn set_signed(contract: &mut SourceScan, signature: &str) -> Result<(), SourceScanError> {
+        entry(accounts(1), "code_hash")
+            .cid(SIGNED_CID)
+            .code_hash(SIGNED_CODE_HASH)
+            .signature(signature)
+            .try_set(contract)
+    }
+
+    #[test]
+    fn attestation_key_is_registered() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        contract.set_attestation_key(KEY.parse().unwrap()).unwrap();
+        assert_eq!(
+            contract.get_attestation_key(accounts(0)),
+            Some(KEY.parse().unwrap())
+        );
+        assert_eq!(
+            attestation_message(&accounts(1), "hash", "cid"),
+            "bob:hash:cid"
+        );
+    }
+
+    #[test]
+    fn valid_signature_is_stored() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_attestation_key(SIGNING_KEY.parse().unwrap())
+            .unwrap();
+
+        set_signed(&mut contract, SIGNATURE).unwrap();
+        assert_eq!(
+            contract.read_contract(&accounts(1)).unwrap().attestation,
+            Some(Attestation {
+                signer: accounts(0),
+                public_key: SIGNING_KEY.parse().unwrap(),
+                signature: SIGNATURE.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn forged_signature_is_rejected() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.set_attestation_key(KEY.parse().unwrap()).unwrap();
+
+        let forged = near_sdk::bs58::encode([7u8; 64]).into_string();
+        assert_eq!(
+            set_signed(&mut contract, &forged),
+            Err(SourceScanError::InvalidSignature(
+                "Invalid attestation signature".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn signing_needs_registered_key() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            set_signed(&mut contract, "signature"),
+            Err(SourceScanError::NotFound(
+                "Register an attestation key before signing verifications".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn independent_attestations_raise_level() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(2)).unwrap();
+        assert_eq!(contract.get_verification_level(accounts(1)), None);
+
+        set_entry(&mut contract, accounts(1), "v1");
+        assert_eq!(
+            contract.get_verification_level(accounts(1)),
+            Some(VerificationLevel::Single)
+        );
+
+        testing_env!(get_context(accounts(2)).build());
+        contract
+            .attest_contract(accounts(1), code_hash("v1"))
+            .unwrap();
+        assert_eq!(
+            contract.get_attesters(accounts(1)),
+            vec![accounts(0), accounts(2)]
+        );
+        assert_eq!(
+            contract.get_verification_level(accounts(1)),
+            Some(VerificationLevel::Multi)
+        );
+
+        // New code needs fresh attestations.
+        set_entry(&mut contract, accounts(1), "v2");
+        assert_eq!(contract.get_attesters(accounts(1)), vec![accounts(2)]);
+        assert_eq!(
+            contract.get_verification_level(accounts(1)),
+            Some(VerificationLevel::Single)
+        );
+    }
+
+    #[test]
+    fn attestation_must_match_code_hash() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "v1");
+
+        assert_eq!(
+            contract.attest_contract(accounts(1), code_hash("v2")),
+            Err(SourceScanError::InvalidInput(
+                "Entry for bob has a different code hash".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn attestations_are_counted_once() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "v1");
+
+        assert_eq!(
+            contract.attest_contract(accounts(1), code_hash("v1")),
+            Err(SourceScanError::AlreadyExists(
+                "Entry is already attested by this verifier".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn entries_stay_pending_until_attested() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(2)).unwrap();
+        contract
+            .set_config(Config {
+                required_attestations: 2,
+                ..Config::default()
+            })
+            .unwrap();
+
+        set_entry(&mut contract, accounts(1), "v1");
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().status,
+            VerificationStatus::Pending
+        );
+        let filters = SearchFilters {
+            status: Some(StatusFilter::Pending),
+            ..SearchFilters::default()
+        };
+        let page = contract.search("bob".to_string(), None, 10, Some(filters), None);
+        assert_eq!(page.results.len(), 1);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract
+            .attest_contract(accounts(1), code_hash("v1"))
+            .unwrap();
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().status,
+            VerificationStatus::Verified
+        );
+    }
+
+    fn nft_mints() -> usize {
+        get_created_receipts()
+            .into_iter()
+            .flat_map(|receipt| receipt.actions)
+            .filter(|action| {
+                matches!(
+                    action,
+                    MockAction::FunctionCallWeight { method_name, .. } if method_name == b"nft_mint"
+                )
+            })
+            .count()
+    }
+
+    #[test]
+    fn pending_entries_mint_and_pay_once_verified() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(2)).unwrap();
+        contract.add_verifier(accounts(3)).unwrap();
+        contract
+            .set_config(Config {
+                required_attestations: 2,
+                badge_contract: Some(accounts(5)),
+                ..Config::default()
+            })
+            .unwrap();
+        testing_env!(get_context(accounts(4))
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+        contract.request_verification(accounts(1)).unwrap();
+
+        // No badge deposit is needed while the entry stays pending.
+        testing_env!(get_context(accounts(2)).build());
+        set_entry(&mut contract, accounts(1), "v1");
+        assert_eq!(nft_mints(), 0);
+        assert_eq!(contract.get_badge(accounts(1)), None);
+        assert_eq!(
+            contract.get_bounties(0, 10),
+            vec![(accounts(1), NearToken::from_near(1))]
+        );
+
+        testing_env!(get_context(accounts(3)).build());
+        assert!(matches!(
+            contract.attest_contract(accounts(1), code_hash("v1")),
+            Err(SourceScanError::InsufficientDeposit(_))
+        ));
+        testing_env!(get_context(accounts(3))
+            .attached_deposit(NearToken::from_millinear(10))
+            .build());
+        contract
+            .attest_contract(accounts(1), code_hash("v1"))
+            .unwrap();
+        assert_eq!(nft_mints(), 1);
+        assert!(contract.get_badge(accounts(1)).is_some());
+        assert!(contract.get_bounties(0, 10).is_empty());
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.contains(r#""event":"bounty_paid""#)
+                && log.contains(r#""verifier":"danny""#)));
+    }
+}
@@ -0,0 +1,261 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId, NearToken, PublicKey};
+
+use crate::{
+    AdminAction, AdvisoryTarget, Config, Council, RiskFlags, Role, Severity, SourceScan,
+    VerificationStatus,
+};
+
+/// A privileged change, as recorded in the admin log.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum AdminLogAction {
+    SetContract {
+        account_id: AccountId,
+    },
+    PurgeContracts {
+        account_ids: Vec<AccountId>,
+    },
+    SetStatus {
+        account_id: AccountId,
+        status: VerificationStatus,
+    },
+    GrantRole {
+        account_id: AccountId,
+        role: Role,
+    },
+    RevokeRole {
+        account_id: AccountId,
+        role: Role,
+    },
+    SetOwner {
+        owner_id: AccountId,
+    },
+    SetConfig {
+        config: Config,
+    },
+    Pause,
+    Unpause,
+    SetContractsForFactory {
+        code_hash: String,
+        account_ids: Vec<AccountId>,
+    },
+    SetCouncil {
+        council: Council,
+    },
+    SetSearchSuffixes {
+        suffixes: Vec<String>,
+    },
+    AddAllowedSuffix {
+        suffix: String,
+    },
+    RemoveAllowedSuffix {
+        suffix: String,
+    },
+    AddBuilderImage {
+        image: String,
+    },
+    RemoveBuilderImage {
+        image: String,
+    },
+    SlashVerifier {
+        account_id: AccountId,
+        amount: NearToken,
+    },
+    ResolveChallenge {
+        challenge_id: u64,
+        upheld: bool,
+    },
+    RenewVerification {
+        account_id: AccountId,
+    },
+    PublishAdvisory {
+        advisory_id: u64,
+        target: AdvisoryTarget,
+        severity: Severity,
+    },
+    WithdrawAdvisory {
+        advisory_id: u64,
+    },
+    AddAudit {
+        account_id: AccountId,
+        report_cid: String,
+    },
+    SetRiskFlags {
+        account_id: AccountId,
+        risk_flags: RiskFlags,
+    },
+    CreateList {
+        name: String,
+    },
+    DeleteList {
+        name: String,
+    },
+    AddToList {
+        name: String,
+        account_ids: Vec<AccountId>,
+    },
+    RemoveFromList {
+        name: String,
+        account_ids: Vec<AccountId>,
+    },
+    MigrateBatch {
+        migrated: u64,
+        remaining: u64,
+    },
+    StageCode {
+        code_hash: String,
+    },
+    DeployStaged {
+        code_hash: String,
+    },
+    ScheduleAction {
+        action_id: u64,
+        action: AdminAction,
+    },
+    CancelAction {
+        action_id: u64,
+    },
+    SetAttestationKey {
+        public_key: PublicKey,
+    },
+}
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct AdminLogEntry {
+    pub action: AdminLogAction,
+    /// Account that made the call; the registry itself for council proposals.
+    pub actor: AccountId,
+    pub timestamp: U64,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Returns admin log entries in the order they were recorded.
+    pub fn get_admin_log(&self, from_index: u64, limit: u64) -> Vec<AdminLogEntry> {
+        (from_index..self.admin_log.len())
+            .take(self.page_limit(limit as usize))
+            .filter_map(|index| self.admin_log.get(index))
+            .collect()
+    }
+}
+
+impl SourceScan {
+    /// Appends `action` to the admin log. Entries are never removed.
+    pub(crate) fn log_admin(&mut self, action: AdminLogAction) {
+        self.admin_log.push(&AdminLogEntry {
+            action,
+            actor: env::predecessor_account_id(),
+            timestamp: U64(env::block_timestamp()),
+        });
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn privileged_actions_are_logged() {
+        testing_env!(get_context(accounts(0)).block_timestamp(100).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(1)).block_timestamp(200).build());
+        set_entry(&mut contract, accounts(2), "code_hash");
+        contract.purge_contract(accounts(2), None).unwrap();
+
+        let log = contract.get_admin_log(0, 10);
+        assert_eq!(log.len(), 3);
+        assert_eq!(
+            log[0],
+            AdminLogEntry {
+                action: AdminLogAction::GrantRole {
+                    account_id: accounts(1),
+                    role: Role::Verifier,
+                },
+                actor: accounts(0),
+                timestamp: U64(100),
+            }
+        );
+        assert_eq!(
+            log[1].action,
+            AdminLogAction::SetContract {
+                account_id: accounts(2)
+            }
+        );
+        assert_eq!(log[2].actor, accounts(1));
+
+        assert_eq!(contract.get_admin_log(2, 10).len(), 1);
+        assert!(contract.get_admin_log(5, 10).is_empty());
+    }
+
+    #[test]
+    fn unchanged_roles_are_not_logged() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.remove_verifier(accounts(1)).unwrap();
+        contract.pause().unwrap();
+        contract.pause().unwrap();
+
+        assert_eq!(contract.get_admin_log(0, 10).len(), 1);
+    }
+
+    #[test]
+    fn registry_settings_are_logged() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        let image = format!("builder@sha256:{}", "0".repeat(64));
+        let replacement = format!("builder@sha256:{}", "1".repeat(64));
+        contract
+            .add_allowed_suffix("near".parse().unwrap())
+            .unwrap();
+        contract
+            .add_allowed_suffix("near".parse().unwrap())
+            .unwrap();
+        contract
+            .remove_allowed_suffix("near".parse().unwrap())
+            .unwrap();
+        contract.add_builder_image(image.clone()).unwrap();
+        contract.add_builder_image(replacement.clone()).unwrap();
+        contract.remove_builder_image(image.clone()).unwrap();
+        contract
+            .set_search_suffixes(vec!["near".parse().unwrap()])
+            .unwrap();
+
+        let actions: Vec<AdminLogAction> = contract
+            .get_admin_log(0, 10)
+            .into_iter()
+            .map(|entry| entry.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                AdminLogAction::AddAllowedSuffix {
+                    suffix: "near".to_string()
+                },
+                AdminLogAction::RemoveAllowedSuffix {
+                    suffix: "near".to_string()
+                },
+                AdminLogAction::AddBuilderImage {
+                    image: image.clone()
+                },
+                AdminLogAction::AddBuilderImage { image: replacement },
+                AdminLogAction::RemoveBuilderImage { image },
+                AdminLogAction::SetSearchSuffixes {
+                    suffixes: vec!["near".to_string()]
+                },
+            ]
+        );
+    }
+}
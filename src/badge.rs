@@ -0,0 +1,227 @@
+use near_sdk::serde_json::{self, json};
+use near_sdk::{env, near_bindgen, AccountId, Gas, NearToken, Promise};
+
+use crate::error::{ensure, SourceScanError};
+use crate::{SourceScan, VerificationStatus};
+
+/// Storage deposit attached to each `nft_mint`, paid by the verifier whose call verifies
+/// the entry.
+const BADGE_MINT_DEPOSIT: NearToken = NearToken::from_millinear(10);
+const GAS_FOR_BADGE_MINT: Gas = Gas::from_tgas(10);
+const GAS_FOR_BADGE_BURN: Gas = Gas::from_tgas(10);
+
+/// One badge per verified code, so re-verifying the same code never mints twice.
+fn badge_token_id(account_id: &AccountId, code_hash: &str) -> String {
+    format!("{}:{}", account_id, code_hash)
+}
+
+fn badge_mint_args(account_id: &AccountId, code_hash: &str) -> serde_json::Value {
+    json!({
+        "token_id": badge_token_id(account_id, code_hash),
+        "receiver_id": account_id,
+        "token_metadata": {
+            "title": "SourceScan verified",
+            "description": format!("Source code of {} is verified by SourceScan", account_id),
+            "extra": json!({ "code_hash": code_hash }).to_string(),
+        },
+    })
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Token id of the badge `account_id` currently holds, if any.
+    pub fn get_badge(&self, account_id: AccountId) -> Option<String> {
+        self.badges.get(&account_id)
+    }
+}
+
+impl SourceScan {
+    /// Whether verifying `account_id` at `code_hash` mints a badge: badges are enabled
+    /// and the account does not hold the badge of that code yet.
+    pub(crate) fn mints_badge(&self, account_id: &AccountId, code_hash: &str) -> bool {
+        self.config.badge_contract.is_some()
+            && self.badges.get(account_id) != Some(badge_token_id(account_id, code_hash))
+    }
+
+    /// Whether a verification of `account_id` at `code_hash` by the caller mints a badge
+    /// right away: an entry left `Pending` gets its badge once attestations verify it.
+    pub(crate) fn verification_mints_badge(&self, account_id: &AccountId, code_hash: &str) -> bool {
+        let same_code = self
+            .read_contract(account_id)
+            .is_some_and(|contract| contract.code_hash == code_hash);
+        self.mints_badge(account_id, code_hash)
+            && self.attested_status(account_id, same_code) == VerificationStatus::Verified
+    }
+
+    /// Checks the caller attached the storage deposit of the badge their call mints, if
+    /// `mints_badge`, so re-verifying accounts cannot drain the registry balance, and
+    /// refunds the rest.
+    pub(crate) fn charge_badge_deposit(&self, mints_badge: bool) -> Result<(), SourceScanError> {
+        let cost = if mints_badge {
+            BADGE_MINT_DEPOSIT
+        } else {
+            NearToken::from_yoctonear(0)
+        };
+        let deposit = env::attached_deposit();
+        ensure!(
+            deposit >= cost,
+            SourceScanError::InsufficientDeposit(format!(
+                "Attach {} to cover the verification badge",
+                cost
+            ))
+        );
+
+        let refund = deposit.saturating_sub(cost);
+        if !refund.is_zero() {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+        Ok(())
+    }
+
+    /// Mints a verification badge to `account_id` on `Config::badge_contract`, if one is
+    /// configured, burning the badge of the code it replaces. The badge contract is
+    /// expected to implement NEP-171 plus `nft_burn`, and to reject transfers so the
+    /// badge stays with the verified account.
+    pub(crate) fn mint_badge(&mut self, account_id: &AccountId, code_hash: &str) {
+        let Some(badge_contract) = self.config.badge_contract.clone() else {
+            return;
+        };
+        self.burn_badge(account_id);
+        self.badges
+            .insert(account_id, &badge_token_id(account_id, code_hash));
+
+        Promise::new(badge_contract).function_call(
+            "nft_mint".to_string(),
+            badge_mint_args(account_id, code_hash)
+                .to_string()
+                .into_bytes(),
+            BADGE_MINT_DEPOSIT,
+            GAS_FOR_BADGE_MINT,
+        );
+    }
+
+    /// Burns the badge `account_id` holds, if any, once its entry is revoked, purged or
+    /// verified against other code.
+    pub(crate) fn burn_badge(&mut self, account_id: &AccountId) {
+        let Some(token_id) = self.badges.remove(account_id) else {
+            return;
+        };
+        let Some(badge_contract) = &self.config.badge_contract else {
+            return;
+        };
+
+        Promise::new(badge_contract.clone()).function_call(
+            "nft_burn".to_string(),
+            json!({ "token_id": token_id }).to_string().into_bytes(),
+            NearToken::from_yoctonear(0),
+            GAS_FOR_BADGE_BURN,
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{code_hash, entry, get_context, set_entry};
+    use crate::Config;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts};
+    use near_sdk::testing_env;
+
+    #[test]
+    fn badge_is_minted_to_verified_account() {
+        assert_eq!(
+            badge_mint_args(&accounts(1), "hash"),
+            json!({
+                "token_id": "bob:hash",
+                "receiver_id": "bob",
+                "token_metadata": {
+                    "title": "SourceScan verified",
+                    "description": "Source code of bob is verified by SourceScan",
+                    "extra": r#"{"code_hash":"hash"}"#,
+                },
+            })
+        );
+    }
+
+    fn created_calls(method: &str) -> Vec<AccountId> {
+        get_created_receipts()
+            .into_iter()
+            .filter(|receipt| {
+                receipt.actions.iter().any(|action| {
+                    matches!(
+                        action,
+                        MockAction::FunctionCallWeight { method_name, .. }
+                            if method_name == method.as_bytes()
+                    )
+                })
+            })
+            .map(|receipt| receipt.receiver_id)
+            .collect()
+    }
+
+    #[test]
+    fn caller_pays_for_badges() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_config(Config {
+                badge_contract: Some(accounts(4)),
+                ..Config::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash").try_set(&mut contract),
+            Err(SourceScanError::InsufficientDeposit(format!(
+                "Attach {} to cover the verification badge",
+                BADGE_MINT_DEPOSIT
+            )))
+        );
+
+        testing_env!(get_context(accounts(0))
+            .attached_deposit(BADGE_MINT_DEPOSIT)
+            .build());
+        set_entry(&mut contract, accounts(1), "code_hash");
+        assert_eq!(created_calls("nft_mint"), vec![accounts(4)]);
+        assert_eq!(
+            contract.get_badge(accounts(1)),
+            Some(format!("bob:{}", code_hash("code_hash")))
+        );
+
+        // The badge of the same code is not minted twice.
+        testing_env!(get_context(accounts(0)).build());
+        set_entry(&mut contract, accounts(1), "code_hash");
+        assert!(created_calls("nft_mint").is_empty());
+    }
+
+    #[test]
+    fn badge_is_burned_on_revoke_and_purge() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_config(Config {
+                badge_contract: Some(accounts(4)),
+                ..Config::default()
+            })
+            .unwrap();
+        testing_env!(get_context(accounts(0))
+            .attached_deposit(BADGE_MINT_DEPOSIT)
+            .build());
+        set_entry(&mut contract, accounts(1), "code_hash");
+        set_entry(&mut contract, accounts(2), "code_hash");
+
+        testing_env!(get_context(accounts(0)).build());
+        contract
+            .revoke_contract(accounts(1), "malicious".to_string())
+            .unwrap();
+        assert_eq!(created_calls("nft_burn"), vec![accounts(4)]);
+        assert_eq!(contract.get_badge(accounts(1)), None);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.purge_contract(accounts(2), None).unwrap();
+        assert_eq!(created_calls("nft_burn"), vec![accounts(4)]);
+        assert_eq!(contract.get_badge(accounts(2)), None);
+    }
+}
@@ -0,0 +1,179 @@
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::indexes::{index_insert, index_remove, key_hash};
+use crate::storage::{charge_storage, refund_storage};
+use crate::{SourceScan, StorageKey};
+
+/// Most entries a user can bookmark.
+const MAX_BOOKMARKS: u64 = 100;
+
+#[near_bindgen]
+impl SourceScan {
+    /// Adds `account_id` to the caller's personal list of entries. The caller pays for
+    /// the storage with the attached deposit, refunded on unbookmarking.
+    #[payable]
+    #[handle_result]
+    pub fn bookmark(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        ensure!(
+            self.contracts.get(&account_id).is_some(),
+            SourceScanError::no_contract(&account_id)
+        );
+        let user = env::predecessor_account_id();
+        let bookmarks = self.bookmarks.get(&user);
+        if bookmarks
+            .as_ref()
+            .is_some_and(|bookmarks| bookmarks.contains(&account_id))
+        {
+            return charge_storage(env::storage_usage());
+        }
+        ensure!(
+            bookmarks.map_or(0, |bookmarks| bookmarks.len()) < MAX_BOOKMARKS,
+            SourceScanError::ContentTooLong(format!(
+                "A user can have at most {} bookmarks",
+                MAX_BOOKMARKS
+            ))
+        );
+
+        let initial_storage = env::storage_usage();
+        index_insert(&mut self.bookmarks, &user, &account_id, || {
+            StorageKey::BookmarksEntries {
+                account_hash: key_hash(user.as_str()),
+            }
+        });
+        charge_storage(initial_storage)
+    }
+
+    #[handle_result]
+    pub fn unbookmark(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+
+        let initial_storage = env::storage_usage();
+        index_remove(
+            &mut self.bookmarks,
+            &env::predecessor_account_id(),
+            &account_id,
+        );
+        refund_storage(initial_storage);
+        Ok(())
+    }
+
+    /// Bookmarked accounts of `user`, oldest bookmark first. Purged entries stay listed
+    /// until unbookmarked.
+    pub fn get_bookmarks(
+        &self,
+        user: AccountId,
+        from_index: usize,
+        limit: usize,
+    ) -> Vec<AccountId> {
+        let limit = self.page_limit(limit);
+        self.bookmarks
+            .get(&user)
+            .map(|bookmarks| bookmarks.iter().skip(from_index).take(limit).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts};
+    use near_sdk::{testing_env, NearToken};
+
+    fn bookmark(
+        contract: &mut SourceScan,
+        user: AccountId,
+        account_id: AccountId,
+    ) -> Result<(), SourceScanError> {
+        testing_env!(get_context(user)
+            .attached_deposit(NearToken::from_millinear(10))
+            .build());
+        contract.bookmark(account_id)
+    }
+
+    #[test]
+    fn users_keep_bookmarks() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        for account_id in [accounts(1), accounts(2)] {
+            set_entry(&mut contract, account_id.clone(), account_id.as_str());
+        }
+
+        bookmark(&mut contract, accounts(3), accounts(2)).unwrap();
+        bookmark(&mut contract, accounts(3), accounts(1)).unwrap();
+        bookmark(&mut contract, accounts(3), accounts(2)).unwrap();
+        assert_eq!(
+            contract.get_bookmarks(accounts(3), 0, 10),
+            vec![accounts(2), accounts(1)]
+        );
+        assert!(contract.get_bookmarks(accounts(4), 0, 10).is_empty());
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.unbookmark(accounts(2)).unwrap();
+        assert_eq!(
+            contract.get_bookmarks(accounts(3), 0, 10),
+            vec![accounts(1)]
+        );
+
+        assert_eq!(
+            bookmark(&mut contract, accounts(3), accounts(5)),
+            Err(SourceScanError::NotFound(
+                "No verified contract for fargo".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn bookmarks_pay_for_storage() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        testing_env!(get_context(accounts(3)).build());
+        assert!(matches!(
+            contract.bookmark(accounts(1)),
+            Err(SourceScanError::InsufficientDeposit(_))
+        ));
+
+        bookmark(&mut contract, accounts(3), accounts(1)).unwrap();
+        testing_env!(get_context(accounts(3)).build());
+        contract.unbookmark(accounts(1)).unwrap();
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(3));
+        assert!(matches!(
+            receipts[0].actions[..],
+            [MockAction::Transfer { .. }]
+        ));
+    }
+
+    #[test]
+    fn bookmarks_are_capped() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        let entries: Vec<AccountId> = (0..=MAX_BOOKMARKS)
+            .map(|i| format!("entry{}.near", i).parse().unwrap())
+            .collect();
+        for account_id in &entries {
+            set_entry(&mut contract, account_id.clone(), account_id.as_str());
+        }
+
+        for account_id in &entries[..MAX_BOOKMARKS as usize] {
+            bookmark(&mut contract, accounts(3), account_id.clone()).unwrap();
+        }
+        assert_eq!(
+            bookmark(
+                &mut contract,
+                accounts(3),
+                entries[MAX_BOOKMARKS as usize].clone()
+            ),
+            Err(SourceScanError::ContentTooLong(
+                "A user can have at most 100 bookmarks".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId, NearToken, Promise};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{BountyData, Event};
+use crate::SourceScan;
+
+/// Smallest deposit accepted by `request_verification`; it also covers the storage a
+/// new bounty entry takes.
+const MIN_BOUNTY: NearToken = NearToken::from_millinear(100);
+/// Time a deposit stays claimable by verifiers before its funder can withdraw it.
+const BOUNTY_LOCK_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+/// One funder's share of the bounty on an account.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub(crate) struct BountyDeposit {
+    funder: AccountId,
+    amount: NearToken,
+    /// Topping up restarts the lock period.
+    deposited_at: U64,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Adds the attached deposit to the bounty for verifying `account_id`. The bounty is
+    /// paid to the verifier, other than the owner, whose `set_contract` call next stores
+    /// an entry for it.
+    #[payable]
+    #[handle_result]
+    pub fn request_verification(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        let deposit = env::attached_deposit();
+        ensure!(
+            deposit >= MIN_BOUNTY,
+            SourceScanError::InsufficientDeposit(format!(
+                "Attach at least {} to request a verification",
+                MIN_BOUNTY
+            ))
+        );
+
+        self.add_bounty(account_id, deposit);
+        Ok(())
+    }
+
+    /// Refunds the caller's deposits towards the bounty on `account_id` once
+    /// `BOUNTY_LOCK_PERIOD` has passed since their last deposit without a payout.
+    #[handle_result]
+    pub fn withdraw_bounty(&mut self, account_id: AccountId) -> Result<Promise, SourceScanError> {
+        let funder = env::predecessor_account_id();
+        let mut deposits = self.bounty_deposits.get(&account_id).unwrap_or_default();
+        let index = deposits
+            .iter()
+            .position(|deposit| deposit.funder == funder)
+            .ok_or_else(|| {
+                SourceScanError::NotFound(format!(
+                    "No bounty deposit from {} for {}",
+                    funder, account_id
+                ))
+            })?;
+        ensure!(
+            env::block_timestamp() >= deposits[index].deposited_at.0 + BOUNTY_LOCK_PERIOD,
+            SourceScanError::Timelocked("Bounty deposit is still locked".to_string())
+        );
+
+        let amount = deposits.swap_remove(index).amount;
+        if deposits.is_empty() {
+            self.bounty_deposits.remove(&account_id);
+        } else {
+            self.bounty_deposits.insert(&account_id, &deposits);
+        }
+        let remaining = self
+            .bounties
+            .get(&account_id)
+            .unwrap_or(NearToken::from_yoctonear(0))
+            .saturating_sub(amount);
+        if remaining.is_zero() {
+            self.bounties.remove(&account_id);
+        } else {
+            self.bounties.insert(&account_id, &remaining);
+        }
+
+        Event::BountyWithdrawn(vec![BountyData {
+            account_id,
+            amount,
+            verifier: None,
+        }])
+        .emit();
+        Ok(Promise::new(funder).transfer(amount))
+    }
+
+    pub fn get_bounties(&self, from_index: usize, limit: usize) -> Vec<(AccountId, NearToken)> {
+        self.bounties
+            .iter()
+            .skip(from_index)
+            .take(self.page_limit(limit))
+            .collect()
+    }
+}
+
+impl SourceScan {
+    /// Adds `amount`, deposited by the caller, to the bounty on `account_id`.
+    pub(crate) fn add_bounty(&mut self, account_id: AccountId, amount: NearToken) {
+        let bounty = self
+            .bounties
+            .get(&account_id)
+            .unwrap_or(NearToken::from_yoctonear(0))
+            .saturating_add(amount);
+        self.bounties.insert(&account_id, &bounty);
+
+        let funder = env::predecessor_account_id();
+        let deposited_at = U64(env::block_timestamp());
+        let mut deposits = self.bounty_deposits.get(&account_id).unwrap_or_default();
+        match deposits.iter_mut().find(|deposit| deposit.funder == funder) {
+            Some(deposit) => {
+                deposit.amount = deposit.amount.saturating_add(amount);
+                deposit.deposited_at = deposited_at;
+            }
+            None => deposits.push(BountyDeposit {
+                funder,
+                amount,
+                deposited_at,
+            }),
+        }
+        self.bounty_deposits.insert(&account_id, &deposits);
+
+        Event::BountyAdded(vec![BountyData {
+            account_id,
+            amount,
+            verifier: None,
+        }])
+        .emit();
+    }
+
+    /// Pays out any bounty on `account_id` to the verifier that just stored its entry. The
+    /// owner can verify without collecting it, so the bounty stays for a verifier.
+    pub(crate) fn pay_bounty(&mut self, account_id: &AccountId) {
+        let verifier = env::predecessor_account_id();
+        if verifier == self.owner_id {
+            return;
+        }
+        let Some(amount) = self.bounties.remove(account_id) else {
+            return;
+        };
+        self.bounty_deposits.remove(account_id);
+
+        Promise::new(verifier.clone()).transfer(amount);
+
+        Event::BountyPaid(vec![BountyData {
+            account_id: account_id.clone(),
+            amount,
+            verifier: Some(verifier),
+        }])
+        .emit();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::{accounts, get_logs};
+    use near_sdk::testing_env;
+
+    #[test]
+    fn bounty_is_paid_to_verifier() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+
+        for _ in 0..2 {
+            testing_env!(get_context(accounts(2))
+                .attached_deposit(NearToken::from_near(1))
+                .build());
+            contract.request_verification(accounts(3)).unwrap();
+        }
+        assert_eq!(
+            contract.get_bounties(0, 10),
+            vec![(accounts(3), NearToken::from_near(2))]
+        );
+
+        testing_env!(get_context(accounts(1)).build());
+        set_entry(&mut contract, accounts(3), "code_hash");
+
+        assert!(contract.get_bounties(0, 10).is_empty());
+        assert!(get_logs()
+            .last()
+            .unwrap()
+            .contains(r#""event":"bounty_paid","data":[{"account_id":"danny","amount":"2000000000000000000000000","verifier":"bob"}]"#));
+    }
+
+    #[test]
+    fn owner_does_not_collect_bounty() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        testing_env!(get_context(accounts(2))
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+        contract.request_verification(accounts(3)).unwrap();
+
+        testing_env!(get_context(accounts(0)).build());
+        set_entry(&mut contract, accounts(3), "code_hash");
+        assert_eq!(
+            contract.get_bounties(0, 10),
+            vec![(accounts(3), NearToken::from_near(1))]
+        );
+    }
+
+    #[test]
+    fn funder_withdraws_after_lock_period() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        for funder in [accounts(1), accounts(2)] {
+            testing_env!(get_context(funder)
+                .attached_deposit(NearToken::from_near(1))
+                .build());
+            contract.request_verification(accounts(3)).unwrap();
+        }
+
+        testing_env!(get_context(accounts(1))
+            .block_timestamp(BOUNTY_LOCK_PERIOD - 1)
+            .build());
+        assert_eq!(
+            contract.withdraw_bounty(accounts(3)).err(),
+            Some(SourceScanError::Timelocked(
+                "Bounty deposit is still locked".to_string()
+            ))
+        );
+
+        testing_env!(get_context(accounts(1))
+            .block_timestamp(BOUNTY_LOCK_PERIOD)
+            .build());
+        contract.withdraw_bounty(accounts(3)).unwrap();
+        assert_eq!(
+            contract.get_bounties(0, 10),
+            vec![(accounts(3), NearToken::from_near(1))]
+        );
+        assert_eq!(
+            contract.withdraw_bounty(accounts(3)).err(),
+            Some(SourceScanError::NotFound(
+                "No bounty deposit from bob for danny".to_string()
+            ))
+        );
+
+        testing_env!(get_context(accounts(2))
+            .block_timestamp(BOUNTY_LOCK_PERIOD)
+            .build());
+        contract.withdraw_bounty(accounts(3)).unwrap();
+        assert!(contract.get_bounties(0, 10).is_empty());
+    }
+
+    #[test]
+    fn bounty_needs_minimum_deposit() {
+        testing_env!(get_context(accounts(0))
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            contract.request_verification(accounts(3)),
+            Err(SourceScanError::InsufficientDeposit(
+                "Attach at least 0.100 NEAR to request a verification".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// A crate whose wasm was verified. Workspaces deploying several crates to one account
+/// list one target per crate.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct BuildTarget {
+    /// Unset for single-crate projects and non-Rust contracts.
+    pub crate_name: Option<String>,
+    pub entry_point: String,
+    /// Cargo features enabled for the build.
+    pub features: Vec<String>,
+}
+
+/// Single-crate target with no features; the layout entries had before targets.
+impl From<String> for BuildTarget {
+    fn from(entry_point: String) -> Self {
+        Self {
+            crate_name: None,
+            entry_point,
+            features: Vec::new(),
+        }
+    }
+}
+
+/// How the verified wasm was built, mirroring the reproducible build metadata
+/// `cargo-near` embeds in contracts.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct BuildInfo {
+    /// Unset for contracts not built with `cargo-near`.
+    pub cargo_near_version: Option<String>,
+    /// Unset for non-Rust contracts.
+    pub rustc_version: Option<String>,
+    pub near_sdk_version: Option<String>,
+    /// Command run inside the builder image, one argument per element.
+    pub build_command: Vec<String>,
+    /// Checksum of the source snapshot the build ran on.
+    pub source_checksum: String,
+    /// Digest of the builder image, as `sha256:<hex>`.
+    pub image_digest: String,
+}
@@ -0,0 +1,211 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId, NearToken, Promise};
+
+use crate::cid::normalize_cid;
+use crate::error::{ensure, SourceScanError};
+use crate::events::{ChallengeData, Event};
+use crate::{AdminLogAction, SourceScan};
+
+/// Stake required to open a challenge; refunded if the challenge is upheld.
+const CHALLENGE_STAKE: NearToken = NearToken::from_near(1);
+pub(crate) const MAX_REASON_LEN: usize = 1024;
+
+/// An open dispute against the stored verification of `account_id`.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Challenge {
+    pub account_id: AccountId,
+    pub challenger: AccountId,
+    pub reason: String,
+    /// IPFS CID of the supporting material, in canonical form.
+    pub evidence_cid: String,
+    pub stake: NearToken,
+    pub created_at: U64,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Contests the verification of `account_id`, staking the attached deposit.
+    #[payable]
+    #[handle_result]
+    pub fn challenge_verification(
+        &mut self,
+        account_id: AccountId,
+        reason: String,
+        evidence_cid: String,
+    ) -> Result<u64, SourceScanError> {
+        self.check_not_paused()?;
+        let stake = env::attached_deposit();
+        ensure!(
+            stake >= CHALLENGE_STAKE,
+            SourceScanError::InsufficientDeposit(format!(
+                "Attach at least {} to open a challenge",
+                CHALLENGE_STAKE
+            ))
+        );
+        ensure!(
+            self.contracts.get(&account_id).is_some(),
+            SourceScanError::no_contract(&account_id)
+        );
+        ensure!(
+            !reason.trim().is_empty(),
+            SourceScanError::InvalidInput("Challenge reason is required".to_string())
+        );
+        ensure!(
+            reason.len() <= MAX_REASON_LEN,
+            SourceScanError::ContentTooLong(format!(
+                "Challenge reason must be at most {} bytes",
+                MAX_REASON_LEN
+            ))
+        );
+        let evidence_cid = normalize_cid(&evidence_cid)
+            .ok_or_else(|| SourceScanError::invalid_cid(&evidence_cid))?;
+
+        let challenge_id = self.challenge_seq;
+        self.challenge_seq += 1;
+        let challenger = env::predecessor_account_id();
+        self.challenges.insert(
+            &challenge_id,
+            &Challenge {
+                account_id: account_id.clone(),
+                challenger,
+                reason,
+                evidence_cid,
+                stake,
+                created_at: U64(env::block_timestamp()),
+            },
+        );
+
+        Event::ChallengeOpened(vec![ChallengeData {
+            challenge_id,
+            account_id,
+            upheld: None,
+        }])
+        .emit();
+
+        Ok(challenge_id)
+    }
+
+    /// Settles a challenge. Upholding it revokes the contested entry with the challenge
+    /// reason and refunds the stake; rejecting it keeps the entry and the stake stays
+    /// with the registry.
+    #[handle_result]
+    pub fn resolve_challenge(
+        &mut self,
+        challenge_id: u64,
+        upheld: bool,
+    ) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        let challenge = self
+            .challenges
+            .remove(&challenge_id)
+            .ok_or_else(|| SourceScanError::NotFound("Challenge not found".to_string()))?;
+
+        if upheld {
+            let revocable = self
+                .read_contract(&challenge.account_id)
+                .is_some_and(|contract| !contract.status.is_revoked());
+            if revocable {
+                self.revoke(&challenge.account_id, challenge.reason);
+            }
+            Promise::new(challenge.challenger).transfer(challenge.stake);
+        }
+        self.log_admin(AdminLogAction::ResolveChallenge {
+            challenge_id,
+            upheld,
+        });
+
+        Event::ChallengeResolved(vec![ChallengeData {
+            challenge_id,
+            account_id: challenge.account_id,
+            upheld: Some(upheld),
+        }])
+        .emit();
+        Ok(())
+    }
+
+    pub fn get_open_challenges(&self, from_index: usize, limit: usize) -> Vec<(u64, Challenge)> {
+        self.challenges
+            .iter()
+            .skip(from_index)
+            .take(self.page_limit(limit))
+            .collect()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn with_entry() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        contract
+    }
+
+    fn challenge(contract: &mut SourceScan) -> u64 {
+        testing_env!(get_context(accounts(2))
+            .attached_deposit(CHALLENGE_STAKE)
+            .build());
+        let challenge_id = contract
+            .challenge_verification(
+                accounts(1),
+                "Sources do not build to the deployed hash".to_string(),
+                cid("evidence"),
+            )
+            .unwrap();
+        testing_env!(get_context(accounts(0)).build());
+        challenge_id
+    }
+
+    #[test]
+    fn upheld_challenge_revokes_entry() {
+        let mut contract = with_entry();
+        let challenge_id = challenge(&mut contract);
+
+        let open = contract.get_open_challenges(0, 10);
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].1.challenger, accounts(2));
+        assert_eq!(open[0].1.evidence_cid, cid("evidence"));
+
+        contract.resolve_challenge(challenge_id, true).unwrap();
+        assert!(contract
+            .get_contract(accounts(1))
+            .unwrap()
+            .status
+            .is_revoked());
+        assert!(contract.get_open_challenges(0, 10).is_empty());
+    }
+
+    #[test]
+    fn rejected_challenge_keeps_entry() {
+        let mut contract = with_entry();
+        let challenge_id = challenge(&mut contract);
+
+        contract.resolve_challenge(challenge_id, false).unwrap();
+        assert!(contract.get_contract(accounts(1)).is_some());
+        assert!(contract.get_open_challenges(0, 10).is_empty());
+    }
+
+    #[test]
+    fn challenger_cannot_resolve() {
+        let mut contract = with_entry();
+        let challenge_id = challenge(&mut contract);
+
+        testing_env!(get_context(accounts(2)).build());
+        assert_eq!(
+            contract.resolve_challenge(challenge_id, true),
+            Err(SourceScanError::Unauthorized(
+                "Only owner can call this method".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use near_sdk::{bs58, near_bindgen, AccountId};
+
+use crate::SourceScan;
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Multicodec of the DAG-PB nodes every CIDv0 implicitly points to.
+const DAG_PB: u64 = 0x70;
+/// Multihash code of sha2-256, the only hash a CIDv0 can carry.
+const SHA2_256: u64 = 0x12;
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[(buffer >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_lowercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+fn base16_decode(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(encoded.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Reads an unsigned LEB128 varint, returning it with the remaining bytes.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value: u64 = 0;
+
+    for (i, &byte) in bytes.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+
+    None
+}
+
+/// Checks that `bytes` is a multihash whose digest length matches its header.
+fn is_multihash(bytes: &[u8]) -> bool {
+    let Some((_, rest)) = read_varint(bytes) else {
+        return false;
+    };
+    let Some((len, digest)) = read_varint(rest) else {
+        return false;
+    };
+
+    len > 0 && digest.len() as u64 == len
+}
+
+/// Validates a CIDv0 or CIDv1 string and returns it as CIDv1 in lowercase base32,
+/// the form IPFS uses by default for gateway subdomains.
+pub(crate) fn normalize_cid(cid: &str) -> Option<String> {
+    if cid.len() == 46 && cid.starts_with("Qm") {
+        let multihash = bs58::decode(cid).into_vec().ok()?;
+        let (code, _) = read_varint(&multihash)?;
+        if code != SHA2_256 || multihash.len() != 34 || !is_multihash(&multihash) {
+            return None;
+        }
+
+        let mut bytes = vec![0x01, DAG_PB as u8];
+        bytes.extend(multihash);
+        return Some(format!("b{}", base32_encode(&bytes)));
+    }
+
+    let mut chars = cid.chars();
+    let prefix = chars.next()?;
+    let encoded = chars.as_str();
+    let bytes = match prefix {
+        'b' | 'B' => base32_decode(encoded)?,
+        'z' => bs58::decode(encoded).into_vec().ok()?,
+        'f' | 'F' => base16_decode(encoded)?,
+        _ => return None,
+    };
+
+    let (version, rest) = read_varint(&bytes)?;
+    let (_, multihash) = read_varint(rest)?;
+    if version != 1 || !is_multihash(multihash) {
+        return None;
+    }
+
+    Some(format!("b{}", base32_encode(&bytes)))
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Returns the canonical CIDv1 (base32) of the sources stored for `account_id`.
+    pub fn get_cid(&self, account_id: AccountId) -> Option<String> {
+        self.read_contract(&account_id)
+            .map(|contract| normalize_cid(&contract.cid).unwrap_or(contract.cid))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, entry, get_context};
+    use crate::SourceScanError;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const CID_V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    const CID_V1: &str = "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34";
+
+    #[test]
+    fn normalizes_to_base32_cid_v1() {
+        assert_eq!(normalize_cid(CID_V0).as_deref(), Some(CID_V1));
+        assert_eq!(normalize_cid(CID_V1).as_deref(), Some(CID_V1));
+        assert_eq!(
+            normalize_cid(&CID_V1.to_uppercase()).as_deref(),
+            Some(CID_V1)
+        );
+
+        let bytes = base32_decode(&CID_V1[1..]).unwrap();
+        let base58 = format!("z{}", bs58::encode(&bytes).into_string());
+        assert_eq!(normalize_cid(&base58).as_deref(), Some(CID_V1));
+        let base16: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            normalize_cid(&format!("f{}", base16)).as_deref(),
+            Some(CID_V1)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_cids() {
+        for cid in [
+            "",
+            "cid",
+            "b",
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0",
+            "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2h",
+            "mAXASIJ0",
+        ] {
+            assert_eq!(normalize_cid(cid), None, "{}", cid);
+        }
+    }
+
+    #[test]
+    fn set_contract_stores_canonical_cid() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        entry(accounts(1), "code_hash")
+            .cid(CID_V0)
+            .set(&mut contract);
+
+        assert_eq!(contract.get_contract(accounts(1)).unwrap().cid, CID_V1);
+        assert_eq!(contract.get_cid(accounts(1)).as_deref(), Some(CID_V1));
+        assert_eq!(contract.get_cid(accounts(2)), None);
+        assert_eq!(normalize_cid(&cid("seed")), Some(cid("seed")));
+    }
+
+    #[test]
+    fn set_contract_rejects_malformed_cid() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .cid("cid")
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidCid(
+                "Invalid CID \"cid\": expected a CIDv0 or CIDv1".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{ClaimData, Event};
+use crate::SourceScan;
+
+#[near_bindgen]
+impl SourceScan {
+    /// Lets a verified account take over its own page by calling in from itself. A
+    /// claimed account maintains its entry, e.g. it can set project metadata.
+    #[handle_result]
+    pub fn claim_contract(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        ensure!(
+            env::predecessor_account_id() == account_id,
+            SourceScanError::Unauthorized(
+                "Only the account itself can claim its entry".to_string()
+            )
+        );
+        ensure!(
+            self.contracts.get(&account_id).is_some(),
+            SourceScanError::no_contract(&account_id)
+        );
+        ensure!(
+            self.claims.get(&account_id).is_none(),
+            SourceScanError::AlreadyExists("Entry is already claimed".to_string())
+        );
+
+        self.claims
+            .insert(&account_id, &U64(env::block_timestamp()));
+
+        Event::ContractClaimed(vec![ClaimData { account_id }]).emit();
+        Ok(())
+    }
+
+    /// Block timestamp at which `account_id` claimed its entry, if it did.
+    pub fn get_claimed_at(&self, account_id: AccountId) -> Option<U64> {
+        self.claims.get(&account_id)
+    }
+}
+
+impl SourceScan {
+    pub(crate) fn check_maintainer(&self, account_id: &AccountId) -> Result<(), SourceScanError> {
+        ensure!(
+            env::predecessor_account_id() == *account_id && self.claims.get(account_id).is_some(),
+            SourceScanError::Unauthorized(
+                "Only the account of a claimed entry can call this method".to_string()
+            )
+        );
+        Ok(())
+    }
+
+    /// Drops the claim of a purged entry; the account can claim again once re-verified.
+    pub(crate) fn clear_claim(&mut self, account_id: &AccountId) {
+        self.claims.remove(account_id);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::{accounts, get_logs};
+    use near_sdk::testing_env;
+
+    fn with_entry() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        contract
+    }
+
+    #[test]
+    fn account_claims_its_entry() {
+        let mut contract = with_entry();
+
+        testing_env!(get_context(accounts(1)).block_timestamp(100).build());
+        contract.claim_contract(accounts(1)).unwrap();
+        assert_eq!(contract.get_claimed_at(accounts(1)), Some(U64(100)));
+        assert_eq!(
+            contract.claim_contract(accounts(1)),
+            Err(SourceScanError::AlreadyExists(
+                "Entry is already claimed".to_string()
+            ))
+        );
+        assert_eq!(
+            get_logs().last().unwrap(),
+            r#"EVENT_JSON:{"standard":"sourcescan","version":"1.0.0","event":"contract_claimed","data":[{"account_id":"bob"}]}"#
+        );
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.purge_contract(accounts(1), None).unwrap();
+        assert_eq!(contract.get_claimed_at(accounts(1)), None);
+    }
+
+    #[test]
+    fn only_the_account_can_claim() {
+        let mut contract = with_entry();
+
+        assert_eq!(
+            contract.claim_contract(accounts(1)),
+            Err(SourceScanError::Unauthorized(
+                "Only the account itself can claim its entry".to_string()
+            ))
+        );
+
+        testing_env!(get_context(accounts(2)).build());
+        assert_eq!(
+            contract.claim_contract(accounts(2)),
+            Err(SourceScanError::NotFound(
+                "No verified contract for charlie".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId, NearToken};
+
+use crate::error::{ensure, SourceScanError};
+use crate::{AdminLogAction, SourceScan};
+
+/// Owner-tunable limits enforced by the mutating methods.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Config {
+    /// Maximum number of tags on a single entry.
+    pub max_tags: u32,
+    /// Maximum length of a tag, in bytes.
+    pub max_tag_len: u32,
+    /// Nanoseconds an `AdminAction` must wait between scheduling and execution.
+    /// Zero disables the timelock and allows the actions to be called directly.
+    pub admin_delay: U64,
+    /// Stake `register_verifier` requires, and that verifiers must hold to call
+    /// `set_contract`. Zero disables staking.
+    pub min_verifier_stake: NearToken,
+    /// Number of distinct verifiers that must attest to an entry for it to be reported
+    /// as `VerificationLevel::Multi`.
+    pub multi_attestation_threshold: u32,
+    /// NEP-171 contract that mints a non-transferable badge to each newly verified
+    /// account. `None` disables badges.
+    pub badge_contract: Option<AccountId>,
+    /// Largest `limit` the paged views honour; larger values are capped to keep view
+    /// calls within the gas limit.
+    pub max_page_size: u32,
+    /// Epochs after which a verified entry is reported as `Expired` unless renewed.
+    /// Zero disables expiry.
+    pub verification_ttl_epochs: U64,
+    /// Most index keys a single `search` call examines before returning a cursor.
+    pub max_search_scan: u32,
+    /// Number of distinct verifiers that must vouch for new code before its entry moves
+    /// from `Pending` to `Verified`. One verifies entries as soon as they are set.
+    pub required_attestations: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_tags: 10,
+            max_tag_len: 32,
+            admin_delay: U64(0),
+            min_verifier_stake: NearToken::from_yoctonear(0),
+            multi_attestation_threshold: 2,
+            badge_contract: None,
+            max_page_size: 100,
+            verification_ttl_epochs: U64(0),
+            max_search_scan: 1000,
+            required_attestations: 1,
+        }
+    }
+}
+
+#[near_bindgen]
+impl SourceScan {
+    #[handle_result]
+    pub fn set_config(&mut self, config: Config) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        ensure!(
+            config.admin_delay.0 >= self.config.admin_delay.0,
+            SourceScanError::Timelocked(
+                "Lowering admin_delay is timelocked; use schedule_action".to_string()
+            )
+        );
+
+        self.apply_config(config)
+    }
+
+    pub fn get_config(&self) -> Config {
+        self.config.clone()
+    }
+}
+
+impl SourceScan {
+    pub(crate) fn apply_config(&mut self, config: Config) -> Result<(), SourceScanError> {
+        ensure!(
+            config.max_tags > 0
+                && config.max_tag_len > 0
+                && config.max_page_size > 0
+                && config.max_search_scan > 0
+                && config.required_attestations > 0,
+            SourceScanError::InvalidInput("Config limits must be greater than zero".to_string())
+        );
+        ensure!(
+            config.multi_attestation_threshold >= 2,
+            SourceScanError::InvalidInput(
+                "multi_attestation_threshold must be at least 2".to_string()
+            )
+        );
+
+        self.config = config.clone();
+        self.log_admin(AdminLogAction::SetConfig { config });
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_context;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn owner_sets_config() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        assert_eq!(contract.get_config(), Config::default());
+
+        let config = Config {
+            max_tags: 3,
+            max_tag_len: 16,
+            admin_delay: U64(0),
+            ..Config::default()
+        };
+        contract.set_config(config.clone()).unwrap();
+        assert_eq!(contract.get_config(), config);
+    }
+
+    #[test]
+    fn verifier_cannot_set_config() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.set_config(Config::default()),
+            Err(SourceScanError::Unauthorized(
+                "Only owner can call this method".to_string()
+            ))
+        );
+    }
+}
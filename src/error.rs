@@ -0,0 +1,79 @@
+use std::fmt;
+
+use near_sdk::serde::Serialize;
+use near_sdk::{serde_json, AccountId, FunctionError};
+
+/// Failure of a mutating method.
+///
+/// The transaction fails with the error serialized as JSON, e.g.
+/// `{"error":"not_found","message":"No verified contract for bob"}`, so integrators can
+/// match on `error` instead of parsing the message.
+#[derive(Serialize, FunctionError, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "error", content = "message", rename_all = "snake_case")]
+pub enum SourceScanError {
+    /// The caller lacks the role or ownership the method requires.
+    Unauthorized(String),
+    NotFound(String),
+    AlreadyExists(String),
+    InvalidCid(String),
+    /// A reason, name or tag is longer than allowed.
+    ContentTooLong(String),
+    InvalidInput(String),
+    InvalidSignature(String),
+    /// The target is not in a state that allows the call, e.g. a closed proposal.
+    InvalidState(String),
+    InsufficientDeposit(String),
+    /// The action must go through `schedule_action` or wait for its delay.
+    Timelocked(String),
+    Paused,
+}
+
+impl SourceScanError {
+    pub(crate) fn invalid_cid(cid: &str) -> Self {
+        Self::InvalidCid(format!("Invalid CID {:?}: expected a CIDv0 or CIDv1", cid))
+    }
+
+    pub(crate) fn invalid_code_hash(code_hash: &str) -> Self {
+        Self::InvalidInput(format!(
+            "Invalid code hash {:?}: expected 32 bytes encoded as base58 or hex",
+            code_hash
+        ))
+    }
+
+    pub(crate) fn no_contract(account_id: &AccountId) -> Self {
+        Self::NotFound(format!("No verified contract for {}", account_id))
+    }
+}
+
+impl fmt::Display for SourceScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&serde_json::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Returns `$err` from the enclosing function unless `$cond` holds.
+macro_rules! ensure {
+    ($cond:expr, $err:expr $(,)?) => {
+        if !$cond {
+            return Err($err);
+        }
+    };
+}
+
+pub(crate) use ensure;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_serialize_with_code() {
+        assert_eq!(
+            SourceScanError::NotFound("Challenge not found".to_string()).to_string(),
+            r#"{"error":"not_found","message":"Challenge not found"}"#
+        );
+        assert_eq!(SourceScanError::Paused.to_string(), r#"{"error":"paused"}"#);
+    }
+}
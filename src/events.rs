@@ -0,0 +1,225 @@
+use near_sdk::json_types::U64;
+use near_sdk::serde::Serialize;
+use near_sdk::{env, serde_json, AccountId, NearToken};
+
+use crate::roles::Role;
+use crate::{AdminAction, AdvisoryTarget, ChangeKind, RiskFlags, VerificationStatus};
+
+/// NEP-297 standard name used for every event emitted by the registry.
+pub const EVENT_STANDARD: &str = "sourcescan";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerChangedData {
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSetData {
+    pub account_id: AccountId,
+    pub code_hash: String,
+    pub cid: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractPurgedData {
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChangedData {
+    pub account_id: AccountId,
+    pub role: Role,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TagChangedData {
+    pub account_id: AccountId,
+    pub tag: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalData {
+    pub proposal_id: u64,
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ActionData {
+    pub action_id: u64,
+    pub action: AdminAction,
+    pub ready_at: U64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseData {
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BountyData {
+    pub account_id: AccountId,
+    pub amount: NearToken,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifier: Option<AccountId>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ChallengeData {
+    pub challenge_id: u64,
+    pub account_id: AccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upheld: Option<bool>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeData {
+    pub account_id: AccountId,
+    pub amount: NearToken,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RiskFlagsData {
+    pub account_id: AccountId,
+    pub risk_flags: RiskFlags,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdvisoryData {
+    pub advisory_id: u64,
+    pub target: AdvisoryTarget,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuditAddedData {
+    pub account_id: AccountId,
+    pub report_cid: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StatusChangedData {
+    pub account_id: AccountId,
+    pub status: VerificationStatus,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AttestedData {
+    pub account_id: AccountId,
+    pub code_hash: String,
+    pub attester: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClaimData {
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReverificationData {
+    pub account_id: AccountId,
+    pub requested_by: AccountId,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WatchData {
+    pub account_id: AccountId,
+    pub kind: ChangeKind,
+    pub watchers: Vec<AccountId>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RenewedData {
+    pub account_id: AccountId,
+    pub epoch: U64,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MigrationRejectedData {
+    pub account_id: AccountId,
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum Event {
+    OwnerChanged(Vec<OwnerChangedData>),
+    ContractSet(Vec<ContractSetData>),
+    ContractPurged(Vec<ContractPurgedData>),
+    RoleGranted(Vec<RoleChangedData>),
+    RoleRevoked(Vec<RoleChangedData>),
+    TagAdded(Vec<TagChangedData>),
+    TagRemoved(Vec<TagChangedData>),
+    ProposalCreated(Vec<ProposalData>),
+    ProposalVoted(Vec<ProposalData>),
+    ProposalExecuted(Vec<ProposalData>),
+    ActionScheduled(Vec<ActionData>),
+    ActionCancelled(Vec<ActionData>),
+    Paused(Vec<PauseData>),
+    Unpaused(Vec<PauseData>),
+    BountyAdded(Vec<BountyData>),
+    BountyPaid(Vec<BountyData>),
+    ChallengeOpened(Vec<ChallengeData>),
+    ChallengeResolved(Vec<ChallengeData>),
+    StatusChanged(Vec<StatusChangedData>),
+    AuditAdded(Vec<AuditAddedData>),
+    AdvisoryPublished(Vec<AdvisoryData>),
+    AdvisoryWithdrawn(Vec<AdvisoryData>),
+    RiskFlagsSet(Vec<RiskFlagsData>),
+    VerifierStaked(Vec<StakeData>),
+    VerifierSlashed(Vec<StakeData>),
+    ContractAttested(Vec<AttestedData>),
+    ContractClaimed(Vec<ClaimData>),
+    ReverificationRequested(Vec<ReverificationData>),
+    WatchedContractChanged(Vec<WatchData>),
+    VerificationRenewed(Vec<RenewedData>),
+    MigrationRejected(Vec<MigrationRejectedData>),
+    BountyWithdrawn(Vec<BountyData>),
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a> {
+    standard: &'static str,
+    version: &'static str,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+impl Event {
+    /// Logs the event as `EVENT_JSON:{...}` so indexers can pick it up.
+    pub fn emit(&self) {
+        let log = EventLog {
+            standard: EVENT_STANDARD,
+            version: EVENT_STANDARD_VERSION,
+            event: self,
+        };
+
+        env::log_str(&format!(
+            "EVENT_JSON:{}",
+            serde_json::to_string(&log).unwrap()
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, RenewedData};
+use crate::{AdminLogAction, ChangeKind, ContractData, SourceScan, VerificationStatus};
+
+#[near_bindgen]
+impl SourceScan {
+    /// Restarts the expiry period of a verified entry, e.g. after a verifier confirmed
+    /// it still matches the deployed code.
+    #[handle_result]
+    pub fn renew_verification(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+
+        let mut contract = self
+            .read_contract(&account_id)
+            .ok_or_else(|| SourceScanError::no_contract(&account_id))?;
+        ensure!(
+            contract.status == VerificationStatus::Verified,
+            SourceScanError::InvalidState("Only verified entries can be renewed".to_string())
+        );
+
+        let epoch = U64(env::epoch_height());
+        contract.verified_at_epoch = epoch;
+        self.touch_contract(&account_id, &mut contract);
+        self.store_contract(&account_id, &contract);
+        self.push_history(&account_id, contract);
+        self.record_change(&account_id, ChangeKind::Renewed);
+        self.log_admin(AdminLogAction::RenewVerification {
+            account_id: account_id.clone(),
+        });
+
+        Event::VerificationRenewed(vec![RenewedData { account_id, epoch }]).emit();
+        Ok(())
+    }
+}
+
+impl SourceScan {
+    /// Reads an entry the way views report it, with expiry applied.
+    pub(crate) fn view_contract(&self, account_id: &AccountId) -> Option<ContractData> {
+        self.read_contract(account_id)
+            .map(|contract| self.expire(contract))
+    }
+
+    /// Reports a verified entry older than `Config::verification_ttl_epochs` as `Expired`.
+    pub(crate) fn expire(&self, mut contract: ContractData) -> ContractData {
+        let ttl = self.config.verification_ttl_epochs.0;
+        if ttl > 0
+            && contract.status == VerificationStatus::Verified
+            && env::epoch_height() >= contract.verified_at_epoch.0.saturating_add(ttl)
+        {
+            contract.status = VerificationStatus::Expired;
+        }
+        contract
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use crate::Config;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn status(contract: &SourceScan) -> VerificationStatus {
+        contract.get_contract(accounts(1)).unwrap().status
+    }
+
+    #[test]
+    fn entries_expire_unless_renewed() {
+        testing_env!(get_context(accounts(0)).epoch_height(10).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_config(Config {
+                verification_ttl_epochs: U64(5),
+                ..Config::default()
+            })
+            .unwrap();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        testing_env!(get_context(accounts(0)).epoch_height(14).build());
+        assert_eq!(status(&contract), VerificationStatus::Verified);
+        contract.renew_verification(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(0)).epoch_height(19).build());
+        assert_eq!(status(&contract), VerificationStatus::Expired);
+        let (contracts, _, _) = contract.get_contracts(0, 10, None);
+        assert_eq!(contracts[0].1.status, VerificationStatus::Expired);
+
+        contract.renew_verification(accounts(1)).unwrap();
+        assert_eq!(status(&contract), VerificationStatus::Verified);
+        assert_eq!(
+            contract
+                .get_contract(accounts(1))
+                .unwrap()
+                .verified_at_epoch,
+            U64(19)
+        );
+        let (history, _, _) = contract.get_contract_history(accounts(1), 0, 10);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2].contract.verified_at_epoch, U64(19));
+    }
+}
@@ -0,0 +1,100 @@
+use near_sdk::borsh;
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+use std::ops::Bound;
+
+use crate::{SourceScan, VersionedContractData};
+
+/// A slice of the registry for off-chain backups and mirrors.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StateChunk {
+    /// Borsh-serialized `VersionedContractData::V1` of each entry, with its source
+    /// metadata inlined so every entry restores on its own.
+    pub entries: Vec<(AccountId, Base64VecU8)>,
+    /// Pass as `start_key` to fetch the next chunk; `None` after the last entry.
+    pub next_key: Option<AccountId>,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Exports up to `limit` entries in account id order, starting at `start_key` or at
+    /// the first entry. Walking `next_key` until it is `None` covers the whole registry
+    /// within the view gas limit of each call.
+    pub fn export_state_chunk(&self, start_key: Option<AccountId>, limit: usize) -> StateChunk {
+        let limit = self.page_limit(limit);
+        let from = start_key.map_or(Bound::Unbounded, |start_key| {
+            Bound::Included(start_key.to_string())
+        });
+
+        let mut account_ids: Vec<AccountId> = self
+            .search_index
+            .range((from, Bound::Unbounded))
+            .take(limit + 1)
+            .map(|(_, account_id)| account_id)
+            .collect();
+        let next_key = if account_ids.len() > limit {
+            account_ids.pop()
+        } else {
+            None
+        };
+
+        let entries = account_ids
+            .into_iter()
+            .filter_map(|account_id| {
+                let contract = self.read_contract(&account_id)?;
+                let bytes = borsh::to_vec(&VersionedContractData::V1(contract.into()))
+                    .expect("Failed to serialize entry");
+                Some((account_id, bytes.into()))
+            })
+            .collect();
+
+        StateChunk { entries, next_key }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, code_hash, entry, get_context};
+    use near_sdk::borsh::BorshDeserialize;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn chunks_cover_the_registry() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        for account_id in [accounts(3), accounts(1), accounts(2)] {
+            entry(account_id.clone(), account_id.as_str())
+                .cid(cid(account_id.as_str()))
+                .set(&mut contract);
+        }
+
+        let chunk = contract.export_state_chunk(None, 2);
+        assert_eq!(chunk.next_key, Some(accounts(3)));
+        assert_eq!(
+            chunk
+                .entries
+                .iter()
+                .map(|(account_id, _)| account_id.clone())
+                .collect::<Vec<_>>(),
+            vec![accounts(1), accounts(2)]
+        );
+
+        let VersionedContractData::V1(entry) =
+            VersionedContractData::try_from_slice(&chunk.entries[0].1 .0).unwrap()
+        else {
+            panic!("exported entries are self-contained");
+        };
+        assert_eq!(entry.cid, cid("bob"));
+        assert_eq!(entry.code_hash, code_hash("bob"));
+
+        let chunk = contract.export_state_chunk(chunk.next_key, 2);
+        assert_eq!(chunk.entries.len(), 1);
+        assert_eq!(chunk.entries[0].0, accounts(3));
+        assert_eq!(chunk.next_key, None);
+    }
+}
@@ -0,0 +1,502 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::json;
+use near_sdk::{env, near_bindgen, AccountId, Gas, NearToken, Promise, PromiseResult};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, ProposalData};
+use crate::migrate::DEPLOY_STAGED_GAS;
+use crate::{AdminLogAction, SourceScan};
+
+/// Gas attached by default to the call a proposal executes.
+const EXECUTE_GAS: Gas = Gas::from_tgas(50);
+/// Most gas a proposal may attach, leaving room for `execute_proposal` and its callback
+/// within one transaction.
+const MAX_EXECUTE_GAS: Gas = Gas::from_tgas(250);
+/// Gas for `on_proposal_executed`, which settles the proposal after its call.
+const EXECUTE_CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+/// Accounts allowed to vote on proposals, and how many approvals one needs.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Council {
+    pub members: Vec<AccountId>,
+    pub threshold: u32,
+}
+
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum ProposalStatus {
+    Open,
+    Rejected,
+    /// Its call succeeded.
+    Executed,
+    /// Approved, but its last call failed; it can be executed again.
+    Approved,
+    /// Its call was sent and has not returned yet.
+    InProgress,
+}
+
+/// A call the registry makes on itself once the council approves it. While governance
+/// mode is on the registry is its own owner, so any owner-only method can be proposed.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct Proposal {
+    pub proposer: AccountId,
+    pub method_name: String,
+    /// JSON arguments for `method_name`.
+    pub args: String,
+    pub approvals: Vec<AccountId>,
+    pub rejections: Vec<AccountId>,
+    pub status: ProposalStatus,
+    pub created_at: U64,
+    /// Gas attached to the call, in gas units.
+    pub gas: U64,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Hands ownership to the registry itself; from then on owner-only methods can only
+    /// run through council proposals.
+    #[handle_result]
+    pub fn enable_governance(&mut self, council: Council) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        self.check_not_timelocked()?;
+
+        self.start_governance(council)
+    }
+
+    #[handle_result]
+    pub fn set_council(&mut self, council: Council) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        self.replace_council(council)
+    }
+
+    pub fn get_council(&self) -> Council {
+        self.council.clone()
+    }
+
+    /// Proposes calling `method_name` with the JSON `args` on the registry, returning
+    /// the proposal id. The proposer's approval is counted. The call gets `gas`, or
+    /// by default enough for `deploy_staged` and its `migrate` when that is the method,
+    /// and `EXECUTE_GAS` otherwise.
+    #[handle_result]
+    pub fn create_proposal(
+        &mut self,
+        method_name: String,
+        args: String,
+        gas: Option<U64>,
+    ) -> Result<u64, SourceScanError> {
+        let proposer = self.check_council_member()?;
+        ensure!(
+            near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(&args).is_ok(),
+            SourceScanError::InvalidInput("Proposal args must be valid JSON".to_string())
+        );
+        let gas = gas.map_or_else(|| default_proposal_gas(&method_name), |gas| gas.0);
+        ensure!(
+            gas > 0 && gas <= MAX_EXECUTE_GAS.as_gas(),
+            SourceScanError::InvalidInput(format!(
+                "Proposal gas must be between 1 and {}",
+                MAX_EXECUTE_GAS.as_gas()
+            ))
+        );
+
+        let proposal_id = self.proposals.len();
+        self.proposals.push(&Proposal {
+            proposer: proposer.clone(),
+            method_name,
+            args,
+            approvals: vec![proposer.clone()],
+            rejections: Vec::new(),
+            status: ProposalStatus::Open,
+            created_at: U64(env::block_timestamp()),
+            gas: U64(gas),
+        });
+
+        Event::ProposalCreated(vec![ProposalData {
+            proposal_id,
+            account_id: proposer,
+        }])
+        .emit();
+
+        Ok(proposal_id)
+    }
+
+    #[handle_result]
+    pub fn vote_proposal(
+        &mut self,
+        proposal_id: u64,
+        approve: bool,
+    ) -> Result<(), SourceScanError> {
+        let voter = self.check_council_member()?;
+        let mut proposal = self.open_proposal(proposal_id)?;
+        ensure!(
+            !proposal.approvals.contains(&voter) && !proposal.rejections.contains(&voter),
+            SourceScanError::AlreadyExists("Already voted on this proposal".to_string())
+        );
+
+        if approve {
+            proposal.approvals.push(voter.clone());
+        } else {
+            proposal.rejections.push(voter.clone());
+            // Reject once the remaining members can no longer reach the threshold.
+            let undecided = self
+                .council
+                .members
+                .len()
+                .saturating_sub(proposal.rejections.len());
+            if undecided < self.council.threshold as usize {
+                proposal.status = ProposalStatus::Rejected;
+            }
+        }
+        self.proposals.replace(proposal_id, &proposal);
+
+        Event::ProposalVoted(vec![ProposalData {
+            proposal_id,
+            account_id: voter,
+        }])
+        .emit();
+        Ok(())
+    }
+
+    /// Runs an approved proposal as a call from the registry to itself. The proposal
+    /// is `InProgress` until `on_proposal_executed` records the outcome.
+    #[handle_result]
+    pub fn execute_proposal(&mut self, proposal_id: u64) -> Result<Promise, SourceScanError> {
+        let executor = self.check_council_member()?;
+        let mut proposal = self
+            .proposals
+            .get(proposal_id)
+            .ok_or_else(|| SourceScanError::NotFound("Proposal not found".to_string()))?;
+        ensure!(
+            matches!(
+                proposal.status,
+                ProposalStatus::Open | ProposalStatus::Approved
+            ),
+            SourceScanError::InvalidState("Proposal cannot be executed now".to_string())
+        );
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|account_id| self.council.members.contains(account_id))
+            .count();
+        ensure!(
+            approvals >= self.council.threshold as usize,
+            SourceScanError::InvalidState("Proposal does not have enough approvals".to_string())
+        );
+
+        proposal.status = ProposalStatus::InProgress;
+        self.proposals.replace(proposal_id, &proposal);
+
+        Ok(Promise::new(env::current_account_id())
+            .function_call(
+                proposal.method_name,
+                proposal.args.into_bytes(),
+                NearToken::from_yoctonear(0),
+                Gas::from_gas(proposal.gas.0),
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_proposal_executed".to_string(),
+                    json!({ "proposal_id": proposal_id, "executor": executor })
+                        .to_string()
+                        .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    EXECUTE_CALLBACK_GAS,
+                ),
+            ))
+    }
+
+    /// Settles a proposal once its call returns: `Executed` on success, back to
+    /// `Approved` on failure so the council can retry it.
+    #[private]
+    pub fn on_proposal_executed(&mut self, proposal_id: u64, executor: AccountId) {
+        let Some(mut proposal) = self.proposals.get(proposal_id) else {
+            return;
+        };
+        let succeeded = matches!(env::promise_result(0), PromiseResult::Successful(_));
+
+        proposal.status = if succeeded {
+            ProposalStatus::Executed
+        } else {
+            ProposalStatus::Approved
+        };
+        self.proposals.replace(proposal_id, &proposal);
+
+        if succeeded {
+            Event::ProposalExecuted(vec![ProposalData {
+                proposal_id,
+                account_id: executor,
+            }])
+            .emit();
+        }
+    }
+
+    pub fn get_proposal(&self, proposal_id: u64) -> Option<Proposal> {
+        self.proposals.get(proposal_id)
+    }
+}
+
+fn default_proposal_gas(method_name: &str) -> u64 {
+    if method_name == "deploy_staged" {
+        DEPLOY_STAGED_GAS.as_gas()
+    } else {
+        EXECUTE_GAS.as_gas()
+    }
+}
+
+impl SourceScan {
+    pub(crate) fn start_governance(&mut self, council: Council) -> Result<(), SourceScanError> {
+        self.replace_council(council)?;
+        self.change_owner(env::current_account_id());
+        Ok(())
+    }
+
+    fn replace_council(&mut self, council: Council) -> Result<(), SourceScanError> {
+        ensure!(
+            council.threshold > 0 && council.threshold as usize <= council.members.len(),
+            SourceScanError::InvalidInput(
+                "Council threshold must be between 1 and the number of members".to_string()
+            )
+        );
+
+        self.council = council.clone();
+        self.log_admin(AdminLogAction::SetCouncil { council });
+        Ok(())
+    }
+
+    fn check_council_member(&self) -> Result<AccountId, SourceScanError> {
+        let account_id = env::predecessor_account_id();
+        ensure!(
+            self.council.members.contains(&account_id),
+            SourceScanError::Unauthorized("Only council members can call this method".to_string())
+        );
+        Ok(account_id)
+    }
+
+    fn open_proposal(&self, proposal_id: u64) -> Result<Proposal, SourceScanError> {
+        let proposal = self
+            .proposals
+            .get(proposal_id)
+            .ok_or_else(|| SourceScanError::NotFound("Proposal not found".to_string()))?;
+        ensure!(
+            proposal.status == ProposalStatus::Open,
+            SourceScanError::InvalidState("Proposal is no longer open".to_string())
+        );
+        Ok(proposal)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_context;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts, testing_env_with_promise_results};
+    use near_sdk::testing_env;
+
+    /// Deployed by `accounts(4)`; the registry itself runs as `accounts(0)`.
+    fn governed() -> SourceScan {
+        testing_env!(get_context(accounts(4)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .enable_governance(Council {
+                members: vec![accounts(1), accounts(2), accounts(3)],
+                threshold: 2,
+            })
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn approved_proposal_calls_registry() {
+        let mut contract = governed();
+        assert_eq!(contract.get_owner(), accounts(0));
+
+        testing_env!(get_context(accounts(1)).build());
+        let args = r#"{"account_id":"eugene"}"#.to_string();
+        let proposal_id = contract
+            .create_proposal("add_verifier".to_string(), args.clone(), None)
+            .unwrap();
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.vote_proposal(proposal_id, true).unwrap();
+        contract.execute_proposal(proposal_id).unwrap();
+
+        let proposal = contract.get_proposal(proposal_id).unwrap();
+        assert_eq!(proposal.method_name, "add_verifier");
+        assert_eq!(proposal.args, args);
+        assert_eq!(proposal.approvals, vec![accounts(1), accounts(2)]);
+        assert_eq!(proposal.status, ProposalStatus::InProgress);
+
+        testing_env_with_promise_results(
+            get_context(accounts(0)).build(),
+            PromiseResult::Successful(Vec::new()),
+        );
+        contract.on_proposal_executed(proposal_id, accounts(2));
+        assert_eq!(
+            contract.get_proposal(proposal_id).unwrap().status,
+            ProposalStatus::Executed
+        );
+    }
+
+    /// Gas attached to the last call of `method` the registry scheduled.
+    fn attached_gas(method: &str) -> Option<Gas> {
+        get_created_receipts()
+            .into_iter()
+            .flat_map(|receipt| receipt.actions)
+            .filter_map(|action| match action {
+                MockAction::FunctionCallWeight {
+                    method_name,
+                    prepaid_gas,
+                    ..
+                } if method_name == method.as_bytes() => Some(prepaid_gas),
+                _ => None,
+            })
+            .next_back()
+    }
+
+    #[test]
+    fn proposals_carry_enough_gas() {
+        let mut contract = governed();
+
+        testing_env!(get_context(accounts(1)).build());
+        let args = r#"{"code_hash":"11111111111111111111111111111111"}"#.to_string();
+        let deploy = contract
+            .create_proposal("deploy_staged".to_string(), args, None)
+            .unwrap();
+        let custom = contract
+            .create_proposal(
+                "add_verifier".to_string(),
+                r#"{"account_id":"eugene"}"#.to_string(),
+                Some(U64(Gas::from_tgas(80).as_gas())),
+            )
+            .unwrap();
+        assert_eq!(
+            contract.create_proposal(
+                "add_verifier".to_string(),
+                "{}".to_string(),
+                Some(U64(Gas::from_tgas(251).as_gas()))
+            ),
+            Err(SourceScanError::InvalidInput(format!(
+                "Proposal gas must be between 1 and {}",
+                MAX_EXECUTE_GAS.as_gas()
+            )))
+        );
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.vote_proposal(deploy, true).unwrap();
+        contract.execute_proposal(deploy).unwrap();
+        assert_eq!(attached_gas("deploy_staged"), Some(DEPLOY_STAGED_GAS));
+
+        contract.vote_proposal(custom, true).unwrap();
+        contract.execute_proposal(custom).unwrap();
+        assert_eq!(attached_gas("add_verifier"), Some(Gas::from_tgas(80)));
+    }
+
+    #[test]
+    fn failed_proposal_can_be_retried() {
+        let mut contract = governed();
+
+        testing_env!(get_context(accounts(1)).build());
+        let proposal_id = contract
+            .create_proposal("set_owner".to_string(), "{}".to_string(), None)
+            .unwrap();
+        testing_env!(get_context(accounts(2)).build());
+        contract.vote_proposal(proposal_id, true).unwrap();
+        contract.execute_proposal(proposal_id).unwrap();
+        assert_eq!(
+            contract.execute_proposal(proposal_id).err(),
+            Some(SourceScanError::InvalidState(
+                "Proposal cannot be executed now".to_string()
+            ))
+        );
+
+        testing_env_with_promise_results(get_context(accounts(0)).build(), PromiseResult::Failed);
+        contract.on_proposal_executed(proposal_id, accounts(2));
+        assert_eq!(
+            contract.get_proposal(proposal_id).unwrap().status,
+            ProposalStatus::Approved
+        );
+
+        testing_env!(get_context(accounts(3)).build());
+        assert!(contract.execute_proposal(proposal_id).is_ok());
+    }
+
+    #[test]
+    fn council_changes_are_logged() {
+        let mut contract = governed();
+        testing_env!(get_context(accounts(0)).build());
+        let council = Council {
+            members: vec![accounts(1), accounts(2)],
+            threshold: 1,
+        };
+        contract.set_council(council.clone()).unwrap();
+
+        let log = contract.get_admin_log(0, 10);
+        assert_eq!(
+            log.last().unwrap().action,
+            AdminLogAction::SetCouncil { council }
+        );
+    }
+
+    #[test]
+    fn rejected_once_threshold_is_unreachable() {
+        let mut contract = governed();
+
+        testing_env!(get_context(accounts(1)).build());
+        let proposal_id = contract
+            .create_proposal("set_owner".to_string(), "{}".to_string(), None)
+            .unwrap();
+        testing_env!(get_context(accounts(2)).build());
+        contract.vote_proposal(proposal_id, false).unwrap();
+        assert_eq!(
+            contract.get_proposal(proposal_id).unwrap().status,
+            ProposalStatus::Open
+        );
+        testing_env!(get_context(accounts(3)).build());
+        contract.vote_proposal(proposal_id, false).unwrap();
+        assert_eq!(
+            contract.get_proposal(proposal_id).unwrap().status,
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn cannot_execute_without_threshold() {
+        let mut contract = governed();
+
+        testing_env!(get_context(accounts(1)).build());
+        let proposal_id = contract
+            .create_proposal("set_owner".to_string(), "{}".to_string(), None)
+            .unwrap();
+        assert_eq!(
+            contract.execute_proposal(proposal_id).err(),
+            Some(SourceScanError::InvalidState(
+                "Proposal does not have enough approvals".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn former_owner_loses_direct_access() {
+        let mut contract = governed();
+
+        testing_env!(get_context(accounts(4)).build());
+        assert_eq!(
+            contract.add_verifier(accounts(5)),
+            Err(SourceScanError::Unauthorized(
+                "Only owner can call this method".to_string()
+            ))
+        );
+    }
+}
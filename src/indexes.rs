@@ -0,0 +1,745 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::SourceScanError;
+use crate::validation::normalize_code_hash;
+use crate::{AdminLogAction, ContractData, Lang, Network, RepoSource, SourceScan, StorageKey};
+
+/// Orderings supported by `get_contracts`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    /// Alphabetical by normalized account name.
+    Name,
+    /// Most recently registered entries first.
+    NewestVerified,
+    /// Most recently re-submitted entries first.
+    RecentlyUpdated,
+}
+
+/// Where an account sits in the ordered indexes backing `SortBy`.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub(crate) struct SortPosition {
+    verified: u64,
+    updated: u64,
+}
+
+/// Secondary index mapping a key to the set of accounts whose entry carries it.
+pub(crate) type AccountIndex<K> = LookupMap<K, UnorderedSet<AccountId>>;
+
+pub(crate) fn index_insert<K: BorshSerialize>(
+    index: &mut AccountIndex<K>,
+    key: &K,
+    account_id: &AccountId,
+    storage_key: impl FnOnce() -> StorageKey,
+) {
+    let mut accounts = index
+        .get(key)
+        .unwrap_or_else(|| UnorderedSet::new(storage_key()));
+    accounts.insert(account_id);
+    index.insert(key, &accounts);
+}
+
+/// Like `index_insert` for several accounts, writing the set under `key` once.
+fn index_insert_all<K: BorshSerialize>(
+    index: &mut AccountIndex<K>,
+    key: &K,
+    account_ids: &[AccountId],
+    storage_key: impl FnOnce() -> StorageKey,
+) {
+    let mut accounts = index
+        .get(key)
+        .unwrap_or_else(|| UnorderedSet::new(storage_key()));
+    accounts.extend(account_ids.iter().cloned());
+    index.insert(key, &accounts);
+}
+
+pub(crate) fn index_remove<K: BorshSerialize>(
+    index: &mut AccountIndex<K>,
+    key: &K,
+    account_id: &AccountId,
+) {
+    let Some(mut accounts) = index.get(key) else {
+        return;
+    };
+
+    accounts.remove(account_id);
+    if accounts.is_empty() {
+        index.remove(key);
+    } else {
+        index.insert(key, &accounts);
+    }
+}
+
+/// Suffixes ignored when ordering account names for `SortBy::Name`.
+const SORT_IGNORED_SUFFIXES: [&str; 2] = [".testnet", ".near"];
+
+/// Suffixes `search` strips from queries until the owner configures others.
+pub(crate) const DEFAULT_SEARCH_SUFFIXES: [&str; 2] = ["testnet", "near"];
+
+/// Normalizes an account name for alphabetical ordering.
+fn sort_name(name: &str) -> String {
+    let name = name.to_lowercase();
+
+    for suffix in SORT_IGNORED_SUFFIXES {
+        if let Some(stripped) = name.strip_suffix(suffix) {
+            return stripped.to_string();
+        }
+    }
+
+    name
+}
+
+/// Name index key; the account id suffix keeps keys unique when two accounts normalize alike.
+fn name_index_key(account_id: &AccountId) -> String {
+    format!("{}\0{}", sort_name(account_id.as_str()), account_id)
+}
+
+/// Accounts `account_id` is a sub-account of, e.g. `signer.near` for `v1.signer.near`.
+/// Top-level accounts are left out, as every entry would be listed under one of them.
+pub(crate) fn parent_accounts(account_id: &AccountId) -> Vec<AccountId> {
+    let name = account_id.as_str();
+    let mut parents: Vec<AccountId> = name
+        .match_indices('.')
+        .filter_map(|(dot, _)| name[dot + 1..].parse().ok())
+        .collect();
+    parents.pop();
+    parents
+}
+
+/// Component index key under which `search` finds `account_id` by its sub-account
+/// `parent`, e.g. `signer.near` for `v1.signer.near`.
+pub(crate) fn component_index_key(parent: &AccountId, account_id: &AccountId) -> String {
+    format!("{}\0{}", parent, account_id)
+}
+
+/// GitHub logins are case-insensitive.
+fn github_owner_key(owner: &str) -> String {
+    owner.to_lowercase()
+}
+
+pub(crate) fn key_hash(key: &str) -> near_sdk::CryptoHash {
+    env::sha256_array(key.as_bytes())
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Lists entries running the code with `code_hash`, given in hex or base58.
+    pub fn get_contracts_by_code_hash(
+        &self,
+        code_hash: String,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        let Some(code_hash) = normalize_code_hash(&code_hash) else {
+            return (Vec::new(), 0, 0);
+        };
+        self.page_index(&self.code_hash_index, &code_hash, from_index, limit)
+    }
+
+    pub fn get_contracts_by_lang(
+        &self,
+        lang: Lang,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        self.page_index(&self.lang_index, &lang, from_index, limit)
+    }
+
+    pub fn get_contracts_by_network(
+        &self,
+        network: Network,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        self.page_index(&self.network_index, &network, from_index, limit)
+    }
+
+    /// Replaces the account suffixes `search` ignores in queries, e.g. `near` so that
+    /// `app.near` finds `app.testnet` too.
+    #[handle_result]
+    pub fn set_search_suffixes(&mut self, suffixes: Vec<AccountId>) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        self.search_suffixes = suffixes.into_iter().map(String::from).collect();
+        self.log_admin(AdminLogAction::SetSearchSuffixes {
+            suffixes: self.search_suffixes.clone(),
+        });
+        Ok(())
+    }
+
+    pub fn get_search_suffixes(&self) -> Vec<String> {
+        self.search_suffixes.clone()
+    }
+
+    /// Lists entries of sub-accounts of `parent_account` at any depth, e.g. every
+    /// `*.sweat.near` entry. Top-level accounts such as `near` list nothing.
+    pub fn get_contracts_under(
+        &self,
+        parent_account: AccountId,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        self.page_index(&self.parent_index, &parent_account, from_index, limit)
+    }
+
+    /// Lists entries whose sources live under the GitHub user or organization `owner`.
+    pub fn get_contracts_by_github_owner(
+        &self,
+        owner: String,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        self.page_index(
+            &self.github_owner_index,
+            &github_owner_key(&owner),
+            from_index,
+            limit,
+        )
+    }
+}
+
+impl SourceScan {
+    /// Updates every secondary index after `contract` was stored for `account_id`,
+    /// replacing `previous` if the account was already registered.
+    pub(crate) fn index_contract(
+        &mut self,
+        account_id: &AccountId,
+        previous: Option<&ContractData>,
+        contract: &ContractData,
+    ) {
+        let verified = self.unindex_previous(account_id, previous);
+        self.index_source(std::slice::from_ref(account_id), contract);
+        self.index_account(account_id, verified, contract);
+    }
+
+    /// Indexes entries that all share the code hash and source record of the first
+    /// one, as factory children do, writing each index they share once per call.
+    pub(crate) fn index_contracts(
+        &mut self,
+        entries: &[(AccountId, Option<ContractData>, ContractData)],
+    ) {
+        let Some((_, _, shared)) = entries.first() else {
+            return;
+        };
+
+        let mut account_ids = Vec::with_capacity(entries.len());
+        for (account_id, previous, contract) in entries {
+            let verified = self.unindex_previous(account_id, previous.as_ref());
+            self.index_account(account_id, verified, contract);
+            account_ids.push(account_id.clone());
+        }
+        self.index_source(&account_ids, shared);
+    }
+
+    /// Unindexes `previous` and returns its registration slot, which re-verifying keeps.
+    fn unindex_previous(
+        &mut self,
+        account_id: &AccountId,
+        previous: Option<&ContractData>,
+    ) -> Option<u64> {
+        let previous = previous?;
+        let verified = self
+            .sort_positions
+            .get(account_id)
+            .map(|position| position.verified);
+        self.unindex_contract(account_id, previous);
+        verified
+    }
+
+    /// Lists `account_ids` under the keys that come from the source record of `contract`.
+    fn index_source(&mut self, account_ids: &[AccountId], contract: &ContractData) {
+        index_insert_all(
+            &mut self.code_hash_index,
+            &contract.code_hash,
+            account_ids,
+            || StorageKey::CodeHashIndexEntries {
+                code_hash: key_hash(&contract.code_hash),
+            },
+        );
+        index_insert_all(&mut self.lang_index, &contract.lang, account_ids, || {
+            StorageKey::LangIndexEntries {
+                lang: key_hash(&contract.lang.to_string()),
+            }
+        });
+        if let Some(RepoSource::Github(github)) = &contract.source {
+            let owner = github_owner_key(&github.owner);
+            index_insert_all(&mut self.github_owner_index, &owner, account_ids, || {
+                StorageKey::GithubOwnerIndexEntries {
+                    owner: key_hash(&owner),
+                }
+            });
+        }
+        self.count_contracts(contract, true, account_ids.len() as u64);
+    }
+
+    /// Lists `account_id` under its network, names and update time.
+    fn index_account(
+        &mut self,
+        account_id: &AccountId,
+        verified: Option<u64>,
+        contract: &ContractData,
+    ) {
+        index_insert(
+            &mut self.network_index,
+            &contract.network,
+            account_id,
+            || StorageKey::NetworkIndexEntries {
+                network: key_hash(&contract.network.to_string()),
+            },
+        );
+        self.name_index
+            .insert(&name_index_key(account_id), account_id);
+        self.search_index
+            .insert(&account_id.to_string(), account_id);
+        for parent in parent_accounts(account_id) {
+            self.component_index
+                .insert(&component_index_key(&parent, account_id), account_id);
+            index_insert(&mut self.parent_index, &parent, account_id, || {
+                StorageKey::ParentIndexEntries {
+                    account_hash: key_hash(parent.as_str()),
+                }
+            });
+        }
+
+        if let Some(tombstone) = self.tombstones.remove(account_id) {
+            self.updated_at_index.remove(&tombstone);
+        }
+        self.sort_seq += 1;
+        let position = SortPosition {
+            verified: verified.unwrap_or(self.sort_seq),
+            updated: self.sort_seq,
+        };
+        self.verified_order.insert(&position.verified, account_id);
+        self.updated_order.insert(&position.updated, account_id);
+        self.updated_at_index
+            .insert(&(contract.updated_at.0, position.updated), account_id);
+        self.sort_positions.insert(account_id, &position);
+    }
+
+    /// Removes `account_id` from every secondary index `contract` was listed in.
+    pub(crate) fn unindex_contract(&mut self, account_id: &AccountId, contract: &ContractData) {
+        index_remove(&mut self.code_hash_index, &contract.code_hash, account_id);
+        index_remove(&mut self.lang_index, &contract.lang, account_id);
+        index_remove(&mut self.network_index, &contract.network, account_id);
+        if let Some(RepoSource::Github(github)) = &contract.source {
+            index_remove(
+                &mut self.github_owner_index,
+                &github_owner_key(&github.owner),
+                account_id,
+            );
+        }
+        self.name_index.remove(&name_index_key(account_id));
+        self.search_index.remove(&account_id.to_string());
+        for parent in parent_accounts(account_id) {
+            self.component_index
+                .remove(&component_index_key(&parent, account_id));
+            index_remove(&mut self.parent_index, &parent, account_id);
+        }
+        self.count_contracts(contract, false, 1);
+
+        if let Some(position) = self.sort_positions.remove(account_id) {
+            self.verified_order.remove(&position.verified);
+            self.updated_order.remove(&position.updated);
+            self.updated_at_index
+                .remove(&(contract.updated_at.0, position.updated));
+        }
+    }
+
+    /// Stamps `contract` with the current block time and moves it in `updated_at_index`,
+    /// for changes that keep the entry's place in `SortBy::RecentlyUpdated`.
+    pub(crate) fn touch_contract(&mut self, account_id: &AccountId, contract: &mut ContractData) {
+        let Some(position) = self.sort_positions.get(account_id) else {
+            return;
+        };
+        self.updated_at_index
+            .remove(&(contract.updated_at.0, position.updated));
+        contract.updated_at = U64(env::block_timestamp());
+        self.updated_at_index
+            .insert(&(contract.updated_at.0, position.updated), account_id);
+    }
+
+    /// Lists a purged account in `updated_at_index` so mirrors syncing by time see it go.
+    pub(crate) fn add_tombstone(&mut self, account_id: &AccountId) {
+        self.sort_seq += 1;
+        let tombstone = (env::block_timestamp(), self.sort_seq);
+        self.updated_at_index.insert(&tombstone, account_id);
+        self.tombstones.insert(account_id, &tombstone);
+    }
+
+    /// Returns one page of account ids in the requested order.
+    pub(crate) fn sorted_account_ids(
+        &self,
+        sort_by: SortBy,
+        from_index: usize,
+        limit: usize,
+    ) -> Vec<AccountId> {
+        match sort_by {
+            SortBy::Name => self
+                .name_index
+                .iter()
+                .skip(from_index)
+                .take(limit)
+                .map(|(_, account_id)| account_id)
+                .collect(),
+            SortBy::NewestVerified => self
+                .verified_order
+                .iter_rev()
+                .skip(from_index)
+                .take(limit)
+                .map(|(_, account_id)| account_id)
+                .collect(),
+            SortBy::RecentlyUpdated => self
+                .updated_order
+                .iter_rev()
+                .skip(from_index)
+                .take(limit)
+                .map(|(_, account_id)| account_id)
+                .collect(),
+        }
+    }
+
+    /// Pages through the accounts listed under `key`, resolving each to its entry.
+    /// Returns the page, the number of pages and the number of accounts under `key`.
+    pub(crate) fn page_index<K: BorshSerialize>(
+        &self,
+        index: &AccountIndex<K>,
+        key: &K,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        let limit = self.page_limit(limit);
+        let Some(accounts) = index.get(key) else {
+            return (Vec::new(), 0, 0);
+        };
+
+        let filtered: Vec<(AccountId, ContractData)> = accounts
+            .iter()
+            .skip(from_index)
+            .take(limit)
+            .filter_map(|account_id| {
+                let contract = self.view_contract(&account_id)?;
+                Some((account_id, contract))
+            })
+            .collect();
+
+        let pages: u64 = self.get_pages(accounts.len(), limit as u64);
+
+        (filtered, pages, accounts.len())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{code_hash, entry, get_context, set_entry};
+    use crate::{RepoData, SearchFilters, SourceScanError};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn by_code_hash(contract: &SourceScan, hash: &str) -> Vec<AccountId> {
+        let (entries, _, _) = contract.get_contracts_by_code_hash(code_hash(hash), 0, 10);
+        entries
+            .into_iter()
+            .map(|(account_id, _)| account_id)
+            .collect()
+    }
+
+    #[test]
+    fn code_hash_index_follows_updates_and_purges() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        set_entry(&mut contract, accounts(1), "hash1");
+        set_entry(&mut contract, accounts(2), "hash1");
+        set_entry(&mut contract, accounts(3), "hash2");
+        assert_eq!(
+            by_code_hash(&contract, "hash1"),
+            vec![accounts(1), accounts(2)]
+        );
+        let (entries, pages, total) = contract.get_contracts_by_code_hash(code_hash("hash1"), 1, 1);
+        assert_eq!((entries[0].0.clone(), pages, total), (accounts(2), 2, 2));
+
+        set_entry(&mut contract, accounts(1), "hash2");
+        assert_eq!(by_code_hash(&contract, "hash1"), vec![accounts(2)]);
+        assert_eq!(
+            by_code_hash(&contract, "hash2"),
+            vec![accounts(3), accounts(1)]
+        );
+
+        contract.purge_contract(accounts(2), None).unwrap();
+        assert!(by_code_hash(&contract, "hash1").is_empty());
+    }
+
+    #[test]
+    fn lang_index_pages_entries() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        entry(accounts(1), "hash1").set(&mut contract);
+        entry(accounts(2), "hash2")
+            .lang(Lang::AssemblyScript)
+            .set(&mut contract);
+        entry(accounts(3), "hash3").set(&mut contract);
+        entry(accounts(4), "hash4").set(&mut contract);
+
+        let (rust, pages, _) = contract.get_contracts_by_lang(Lang::Rust, 0, 2);
+        assert_eq!(pages, 2);
+        assert_eq!(rust.len(), 2);
+        assert_eq!(rust[0].0, accounts(1));
+
+        entry(accounts(1), "hash1")
+            .lang(Lang::AssemblyScript)
+            .set(&mut contract);
+        let (rust, pages, _) = contract.get_contracts_by_lang(Lang::Rust, 0, 10);
+        assert_eq!(pages, 1);
+        assert_eq!(
+            rust.iter()
+                .map(|(account_id, _)| account_id.clone())
+                .collect::<Vec<_>>(),
+            vec![accounts(4), accounts(3)]
+        );
+
+        let (go, pages, _) = contract.get_contracts_by_lang(Lang::Go, 0, 10);
+        assert!(go.is_empty());
+        assert_eq!(pages, 0);
+    }
+
+    fn on(network: Network) -> SearchFilters {
+        SearchFilters {
+            network: Some(network),
+            ..SearchFilters::default()
+        }
+    }
+
+    #[test]
+    fn network_index_filters_lists_and_search() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        entry("foo.near".parse().unwrap(), "foo.near").set(&mut contract);
+        entry("foo.testnet".parse().unwrap(), "foo.testnet")
+            .network(Network::Testnet)
+            .set(&mut contract);
+        entry("foobar.near".parse().unwrap(), "foobar.near")
+            .network(Network::Custom("localnet".to_string()))
+            .set(&mut contract);
+
+        let (testnet, pages, total_count) =
+            contract.get_contracts_by_network(Network::Testnet, 0, 10);
+        assert_eq!((pages, total_count), (1, 1));
+        assert_eq!(testnet[0].0, "foo.testnet");
+        assert_eq!(testnet[0].1.network, Network::Testnet);
+
+        let results = contract
+            .search(
+                "foo".to_string(),
+                None,
+                10,
+                Some(on(Network::Mainnet)),
+                None,
+            )
+            .results;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "foo.near");
+
+        // Re-verifying without a network keeps the stored one.
+        entry("foo.testnet".parse().unwrap(), "foo.testnet").set(&mut contract);
+        assert_eq!(
+            contract.get_contracts_by_network(Network::Testnet, 0, 10).2,
+            1
+        );
+
+        entry("foo.testnet".parse().unwrap(), "foo.testnet")
+            .network(Network::Mainnet)
+            .set(&mut contract);
+        assert_eq!(
+            contract.get_contracts_by_network(Network::Mainnet, 0, 10).2,
+            2
+        );
+        assert!(contract
+            .search(
+                "foo".to_string(),
+                None,
+                10,
+                Some(on(Network::Testnet)),
+                None
+            )
+            .results
+            .is_empty());
+    }
+
+    #[test]
+    fn custom_network_names_are_validated() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "hash")
+                .network(Network::Custom("testnet".to_string()))
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Invalid network \"testnet\": expected lowercase letters, digits and dashes"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn get_contracts_under_lists_sub_accounts() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        for account_id in [
+            "sweat.near",
+            "token.sweat.near",
+            "v2.claim.sweat.near",
+            "sweat.testnet",
+            "near",
+        ] {
+            set_entry(&mut contract, account_id.parse().unwrap(), account_id);
+        }
+
+        let (entries, pages, total_count) =
+            contract.get_contracts_under("sweat.near".parse().unwrap(), 0, 10);
+        assert_eq!((pages, total_count), (1, 2));
+        assert_eq!(
+            entries
+                .iter()
+                .map(|(account_id, _)| account_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["token.sweat.near", "v2.claim.sweat.near"]
+        );
+        assert_eq!(
+            contract
+                .get_contracts_under("claim.sweat.near".parse().unwrap(), 0, 10)
+                .2,
+            1
+        );
+        assert_eq!(
+            contract
+                .get_contracts_under("near".parse().unwrap(), 0, 10)
+                .2,
+            0
+        );
+
+        contract
+            .purge_contract("token.sweat.near".parse().unwrap(), None)
+            .unwrap();
+        assert_eq!(
+            contract
+                .get_contracts_under("sweat.near".parse().unwrap(), 0, 10)
+                .2,
+            1
+        );
+    }
+
+    #[test]
+    fn only_owner_sets_search_suffixes() {
+        testing_env!(get_context(accounts(1)).build());
+        let mut contract = SourceScan::with_owner(accounts(0));
+
+        assert_eq!(
+            contract.set_search_suffixes(Vec::new()),
+            Err(SourceScanError::Unauthorized(
+                "Only owner can call this method".to_string()
+            ))
+        );
+    }
+
+    fn page_ids(contract: &SourceScan, sort_by: SortBy) -> Vec<AccountId> {
+        let (entries, _, _) = contract.get_contracts(0, 10, Some(sort_by));
+        entries
+            .into_iter()
+            .map(|(account_id, _)| account_id)
+            .collect()
+    }
+
+    #[test]
+    fn get_contracts_sorted() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        set_entry(&mut contract, accounts(3), "hash3");
+        set_entry(&mut contract, accounts(1), "hash1");
+        set_entry(&mut contract, accounts(2), "hash2");
+        set_entry(&mut contract, accounts(3), "hash3-v2");
+
+        assert_eq!(
+            page_ids(&contract, SortBy::Name),
+            vec![accounts(1), accounts(2), accounts(3)]
+        );
+        assert_eq!(
+            page_ids(&contract, SortBy::NewestVerified),
+            vec![accounts(2), accounts(1), accounts(3)]
+        );
+        assert_eq!(
+            page_ids(&contract, SortBy::RecentlyUpdated),
+            vec![accounts(3), accounts(2), accounts(1)]
+        );
+
+        contract.purge_contract(accounts(2), None).unwrap();
+        assert_eq!(
+            page_ids(&contract, SortBy::NewestVerified),
+            vec![accounts(1), accounts(3)]
+        );
+
+        let (entries, pages, _) = contract.get_contracts(1, 1, Some(SortBy::RecentlyUpdated));
+        assert_eq!(pages, 2);
+        assert_eq!(entries[0].0, accounts(1));
+    }
+
+    fn github(owner: &str) -> RepoSource {
+        RepoSource::Github(RepoData {
+            owner: owner.to_string(),
+            repo: "repo".to_string(),
+            sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            path: None,
+            git_ref: None,
+        })
+    }
+
+    #[test]
+    fn github_owner_index_follows_updates() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        entry(accounts(1), "hash1")
+            .source(github("SourceScan"))
+            .set(&mut contract);
+        entry(accounts(2), "hash2")
+            .source(github("sourcescan"))
+            .set(&mut contract);
+        entry(accounts(3), "hash3")
+            .source(github("near"))
+            .set(&mut contract);
+        entry(accounts(4), "hash4").set(&mut contract);
+
+        let (entries, pages, total_count) =
+            contract.get_contracts_by_github_owner("SOURCESCAN".to_string(), 0, 10);
+        assert_eq!(pages, 1);
+        assert_eq!(total_count, 2);
+        assert_eq!(
+            entries
+                .into_iter()
+                .map(|(account_id, _)| account_id)
+                .collect::<Vec<_>>(),
+            vec![accounts(1), accounts(2)]
+        );
+
+        entry(accounts(1), "hash1").set(&mut contract);
+        contract.purge_contract(accounts(3), None).unwrap();
+        let (entries, _, _) =
+            contract.get_contracts_by_github_owner("sourcescan".to_string(), 0, 10);
+        assert_eq!(entries[0].0, accounts(2));
+        assert_eq!(entries.len(), 1);
+        let (entries, _, _) = contract.get_contracts_by_github_owner("near".to_string(), 0, 10);
+        assert!(entries.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// Source language of a verified contract.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum Lang {
+    Rust,
+    AssemblyScript,
+    JavaScript,
+    TypeScript,
+    Go,
+    /// Any language without a dedicated variant, by its display name.
+    Other(String),
+}
+
+impl Lang {
+    const KNOWN: [Lang; 5] = [
+        Lang::Rust,
+        Lang::AssemblyScript,
+        Lang::JavaScript,
+        Lang::TypeScript,
+        Lang::Go,
+    ];
+
+    /// Matches `name` against the known languages, ignoring case and surrounding whitespace.
+    pub(crate) fn known(name: &str) -> Option<Lang> {
+        let name = name.trim();
+        Self::KNOWN
+            .into_iter()
+            .find(|lang| lang.to_string().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Lang::Rust => "Rust",
+            Lang::AssemblyScript => "AssemblyScript",
+            Lang::JavaScript => "JavaScript",
+            Lang::TypeScript => "TypeScript",
+            Lang::Go => "Go",
+            Lang::Other(name) => name,
+        };
+        f.write_str(name)
+    }
+}
+
+/// Parses free-form names stored before `Lang` existed.
+impl From<String> for Lang {
+    fn from(name: String) -> Self {
+        Lang::known(&name).unwrap_or_else(|| Lang::Other(name.trim().to_string()))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_legacy_names() {
+        assert_eq!(Lang::from("Rust".to_string()), Lang::Rust);
+        assert_eq!(Lang::from("rust ".to_string()), Lang::Rust);
+        assert_eq!(Lang::from("typescript".to_string()), Lang::TypeScript);
+        assert_eq!(
+            Lang::from(" Solidity".to_string()),
+            Lang::Other("Solidity".to_string())
+        );
+    }
+
+    #[test]
+    fn serializes_as_variant_names() {
+        assert_eq!(
+            near_sdk::serde_json::to_string(&Lang::AssemblyScript).unwrap(),
+            r#""AssemblyScript""#
+        );
+        assert_eq!(
+            near_sdk::serde_json::from_str::<Lang>(r#"{"Other":"Solidity"}"#).unwrap(),
+            Lang::Other("Solidity".to_string())
+        );
+        assert!(near_sdk::serde_json::from_str::<Lang>(r#""rust ""#).is_err());
+    }
+}
@@ -1,41 +1,284 @@
 use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{UnorderedMap};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::U64;
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, near_bindgen, AccountId, BorshStorageKey, require, log};
+use near_sdk::{env, near_bindgen, AccountId, BorshStorageKey, CryptoHash, NearToken, PublicKey};
 
-#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
-#[serde(crate = "near_sdk::serde")]
-#[borsh(crate = "near_sdk::borsh")]
-pub struct GithubData {
-    pub owner: String,
-    pub repo: String,
-    pub sha: String,
-}
+mod advisory;
+mod attestation;
+mod audit;
+mod badge;
+mod bookmarks;
+mod bounty;
+mod build;
+mod challenge;
+mod cid;
+mod claim;
+mod config;
+mod error;
+mod events;
+mod expiry;
+mod export;
+mod governance;
+mod indexes;
+mod lang;
+mod lists;
+mod network;
+mod migrate;
+mod nep330;
+mod pause;
+mod project;
+mod records;
+mod reports;
+mod reverification;
+mod risk;
+mod roles;
+mod search;
+mod source;
+mod staking;
+mod stats;
+mod status;
+mod storage;
+mod summary;
+mod sync;
+mod tags;
+mod timelock;
+mod validation;
+mod watch;
+
+pub use advisory::{Advisory, AdvisoryTarget, Severity};
+pub use attestation::{Attestation, VerificationLevel};
+pub use audit::{AdminLogAction, AdminLogEntry};
+pub use build::{BuildInfo, BuildTarget};
+pub use challenge::Challenge;
+pub use config::Config;
+pub use error::SourceScanError;
+pub use export::StateChunk;
+pub use governance::{Council, Proposal, ProposalStatus};
+pub use indexes::SortBy;
+pub use lang::Lang;
+pub use nep330::{Nep330Metadata, Standard};
+pub use network::Network;
+pub use project::ProjectMetadata;
+pub use records::SourceRecord;
+pub use reports::AuditReport;
+pub use risk::RiskFlags;
+pub use roles::Role;
+pub use search::{SearchCursor, SearchFilters, SearchPage, StatusFilter};
+pub use source::{GitData, RepoData, RepoSource};
+pub use staking::VerifierStake;
+pub use stats::Stats;
+pub use status::VerificationStatus;
+pub use summary::ContractSummary;
+pub use sync::ChangeKind;
+pub use timelock::{AdminAction, ScheduledAction};
 
-#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+use bounty::BountyDeposit;
+use cid::normalize_cid;
+use events::{ContractPurgedData, ContractSetData, Event, OwnerChangedData};
+use indexes::{AccountIndex, SortPosition, DEFAULT_SEARCH_SUFFIXES};
+use migrate::ContractDataV0;
+use records::{AccountEntry, ContractDataV1, SnapshotSource};
+use sync::ChangeRecord;
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 #[borsh(crate = "near_sdk::borsh")]
 pub struct ContractData {
     pub cid: String,
-    pub lang: String,
-    pub entry_point: String,
+    pub lang: Lang,
+    pub build_targets: Vec<BuildTarget>,
     pub code_hash: String,
     pub builder_image: String,
-    pub github: Option<GithubData>,
+    pub source: Option<RepoSource>,
+    pub build_info: Option<BuildInfo>,
+    pub network: Network,
+    /// Block timestamp of the first verification of this account.
+    pub created_at: U64,
+    /// Block timestamp of the latest `set_contract` call for this account.
+    pub updated_at: U64,
+    pub verified_at_block_height: U64,
+    /// Epoch of the latest verification or `renew_verification` call.
+    pub verified_at_epoch: U64,
+    pub status: VerificationStatus,
+    /// Unset until a verifier assesses the entry; cleared when its code changes.
+    pub risk_flags: Option<RiskFlags>,
+    /// Signature of the verifier that stored the entry, if it signed it.
+    pub attestation: Option<Attestation>,
+    /// Block timestamp of the latest `mark_stale` check since the entry was verified.
+    pub last_checked_at: Option<U64>,
+    /// Code hash the account had deployed at that check.
+    pub onchain_hash_at_check: Option<String>,
+}
+
+/// Storage wrapper for `ContractData`.
+///
+/// Variants hold frozen layouts. When a layout changes after a release, add a variant
+/// for the new one and convert it in `resolve_contract`, so existing entries keep
+/// deserializing without a full state rewrite.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+// Only ever held briefly while reading or writing an entry.
+#[allow(clippy::large_enum_variant)]
+pub enum VersionedContractData {
+    /// Full entry; written by history snapshots before `V3`, and by `export_state_chunk`.
+    V1(ContractDataV1),
+    /// Entry whose source metadata lives in the shared `SourceRecord` of its code hash.
+    V2(AccountEntry),
+    /// History snapshot whose source metadata lives in the `SnapshotSource` keyed by
+    /// `source`.
+    V3 { entry: AccountEntry, source: CryptoHash },
+}
+
+/// A verification recorded by `set_contract`, kept even after the entry is overwritten.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSnapshot {
+    pub contract: ContractData,
+    pub timestamp: U64,
+    pub block_height: U64,
+}
+
+/// Storage form of `ContractSnapshot`.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+struct HistoryRecord {
+    contract: VersionedContractData,
+    timestamp: U64,
+    block_height: U64,
 }
 
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize)]
 #[borsh(crate = "near_sdk::borsh")]
 pub struct SourceScan {
     owner_id: AccountId,
-    contracts: UnorderedMap<AccountId, ContractData>,
+    contracts: UnorderedMap<AccountId, VersionedContractData>,
+    history: LookupMap<AccountId, Vector<HistoryRecord>>,
+    verifiers: UnorderedSet<AccountId>,
+    moderators: UnorderedSet<AccountId>,
+    code_hash_index: AccountIndex<String>,
+    lang_index: AccountIndex<Lang>,
+    network_index: AccountIndex<Network>,
+    name_index: TreeMap<String, AccountId>,
+    search_index: TreeMap<String, AccountId>,
+    component_index: TreeMap<String, AccountId>,
+    search_suffixes: Vec<String>,
+    parent_index: AccountIndex<AccountId>,
+    sort_seq: u64,
+    sort_positions: LookupMap<AccountId, SortPosition>,
+    verified_order: TreeMap<u64, AccountId>,
+    updated_order: TreeMap<u64, AccountId>,
+    updated_at_index: TreeMap<(u64, u64), AccountId>,
+    allowed_suffixes: Vec<String>,
+    builder_images: UnorderedSet<String>,
+    changes: Vector<ChangeRecord>,
+    lang_counts: UnorderedMap<Lang, u64>,
+    builder_image_counts: UnorderedMap<String, u64>,
+    github_owner_index: AccountIndex<String>,
+    tag_index: AccountIndex<String>,
+    contract_tags: LookupMap<AccountId, Vec<String>>,
+    config: Config,
+    council: Council,
+    proposals: Vector<Proposal>,
+    action_seq: u64,
+    scheduled_actions: UnorderedMap<u64, ScheduledAction>,
+    paused: bool,
+    bounties: UnorderedMap<AccountId, NearToken>,
+    challenge_seq: u64,
+    challenges: UnorderedMap<u64, Challenge>,
+    sources: LookupMap<String, SourceRecord>,
+    snapshot_sources: LookupMap<CryptoHash, SnapshotSource>,
+    audits: LookupMap<AccountId, Vec<AuditReport>>,
+    advisories: Vector<Advisory>,
+    active_advisories: UnorderedSet<u64>,
+    advisory_index: LookupMap<AdvisoryTarget, Vec<u64>>,
+    verifier_stakes: UnorderedMap<AccountId, VerifierStake>,
+    attestation_keys: LookupMap<AccountId, PublicKey>,
+    attesters: LookupMap<AccountId, Vec<AccountId>>,
+    claims: LookupMap<AccountId, U64>,
+    project_metadata: LookupMap<AccountId, ProjectMetadata>,
+    watchers: AccountIndex<AccountId>,
+    watched: AccountIndex<AccountId>,
+    bookmarks: AccountIndex<AccountId>,
+    curated_lists: AccountIndex<String>,
+    curated_list_names: UnorderedSet<String>,
+    pending_reverifications: UnorderedMap<AccountId, U64>,
+    admin_log: Vector<AdminLogEntry>,
+    /// Entries of the first deployed version still waiting for `migrate_batch`.
+    legacy_contracts: Option<UnorderedMap<AccountId, ContractDataV0>>,
+    badges: LookupMap<AccountId, String>,
+    /// `updated_at_index` key of each purged account's tombstone, dropped when the
+    /// account is verified again.
+    tombstones: LookupMap<AccountId, (u64, u64)>,
+    bounty_deposits: LookupMap<AccountId, Vec<BountyDeposit>>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
 #[borsh(crate = "near_sdk::borsh")]
 enum StorageKey {
+    /// Entries written before `ContractData` was versioned; drained by `migrate_batch`.
+    #[allow(dead_code)]
     SourceScanRecords,
+    ContractHistory,
+    ContractHistoryEntries { account_hash: CryptoHash },
+    Verifiers,
+    Moderators,
+    CodeHashIndex,
+    CodeHashIndexEntries { code_hash: CryptoHash },
+    LangIndex,
+    LangIndexEntries { lang: CryptoHash },
+    NameIndex,
+    SortPositions,
+    VerifiedOrder,
+    UpdatedOrder,
+    Contracts,
+    BuilderImages,
+    Changes,
+    LangCounts,
+    BuilderImageCounts,
+    GithubOwnerIndex,
+    GithubOwnerIndexEntries { owner: CryptoHash },
+    TagIndex,
+    TagIndexEntries { tag: CryptoHash },
+    ContractTags,
+    Proposals,
+    ScheduledActions,
+    Bounties,
+    Challenges,
+    AdminLog,
+    Sources,
+    Audits,
+    Advisories,
+    ActiveAdvisories,
+    AdvisoryIndex,
+    VerifierStakes,
+    AttestationKeys,
+    Attesters,
+    Claims,
+    ProjectMetadata,
+    Watchers,
+    WatchersEntries { account_hash: CryptoHash },
+    Watched,
+    WatchedEntries { account_hash: CryptoHash },
+    Bookmarks,
+    BookmarksEntries { account_hash: CryptoHash },
+    CuratedLists,
+    CuratedListEntries { name: CryptoHash },
+    CuratedListNames,
+    PendingReverifications,
+    NetworkIndex,
+    NetworkIndexEntries { network: CryptoHash },
+    SearchIndex,
+    ParentIndex,
+    ParentIndexEntries { account_hash: CryptoHash },
+    ComponentIndex,
+    UpdatedAtIndex,
+    SnapshotSources,
+    Badges,
+    Tombstones,
+    BountyDeposits,
 }
 
 impl Default for SourceScan {
@@ -50,104 +293,399 @@ impl SourceScan {
     pub fn new() -> Self {
         assert!(!env::state_exists(), "Already initialized");
         
+        Self::with_owner(env::predecessor_account_id())
+    }
+
+    fn with_owner(owner_id: AccountId) -> Self {
         Self {
-            owner_id: env::predecessor_account_id(),
-            contracts: UnorderedMap::new(StorageKey::SourceScanRecords),
+            owner_id,
+            contracts: UnorderedMap::new(StorageKey::Contracts),
+            history: LookupMap::new(StorageKey::ContractHistory),
+            verifiers: UnorderedSet::new(StorageKey::Verifiers),
+            moderators: UnorderedSet::new(StorageKey::Moderators),
+            code_hash_index: LookupMap::new(StorageKey::CodeHashIndex),
+            lang_index: LookupMap::new(StorageKey::LangIndex),
+            network_index: LookupMap::new(StorageKey::NetworkIndex),
+            name_index: TreeMap::new(StorageKey::NameIndex),
+            search_index: TreeMap::new(StorageKey::SearchIndex),
+            component_index: TreeMap::new(StorageKey::ComponentIndex),
+            search_suffixes: DEFAULT_SEARCH_SUFFIXES.map(String::from).to_vec(),
+            parent_index: LookupMap::new(StorageKey::ParentIndex),
+            sort_seq: 0,
+            sort_positions: LookupMap::new(StorageKey::SortPositions),
+            verified_order: TreeMap::new(StorageKey::VerifiedOrder),
+            updated_order: TreeMap::new(StorageKey::UpdatedOrder),
+            updated_at_index: TreeMap::new(StorageKey::UpdatedAtIndex),
+            allowed_suffixes: Vec::new(),
+            builder_images: UnorderedSet::new(StorageKey::BuilderImages),
+            changes: Vector::new(StorageKey::Changes),
+            lang_counts: UnorderedMap::new(StorageKey::LangCounts),
+            builder_image_counts: UnorderedMap::new(StorageKey::BuilderImageCounts),
+            github_owner_index: LookupMap::new(StorageKey::GithubOwnerIndex),
+            tag_index: LookupMap::new(StorageKey::TagIndex),
+            contract_tags: LookupMap::new(StorageKey::ContractTags),
+            config: Config::default(),
+            council: Council {
+                members: Vec::new(),
+                threshold: 0,
+            },
+            proposals: Vector::new(StorageKey::Proposals),
+            action_seq: 0,
+            scheduled_actions: UnorderedMap::new(StorageKey::ScheduledActions),
+            paused: false,
+            bounties: UnorderedMap::new(StorageKey::Bounties),
+            challenge_seq: 0,
+            challenges: UnorderedMap::new(StorageKey::Challenges),
+            sources: LookupMap::new(StorageKey::Sources),
+            snapshot_sources: LookupMap::new(StorageKey::SnapshotSources),
+            audits: LookupMap::new(StorageKey::Audits),
+            advisories: Vector::new(StorageKey::Advisories),
+            active_advisories: UnorderedSet::new(StorageKey::ActiveAdvisories),
+            advisory_index: LookupMap::new(StorageKey::AdvisoryIndex),
+            verifier_stakes: UnorderedMap::new(StorageKey::VerifierStakes),
+            attestation_keys: LookupMap::new(StorageKey::AttestationKeys),
+            attesters: LookupMap::new(StorageKey::Attesters),
+            claims: LookupMap::new(StorageKey::Claims),
+            project_metadata: LookupMap::new(StorageKey::ProjectMetadata),
+            watchers: LookupMap::new(StorageKey::Watchers),
+            watched: LookupMap::new(StorageKey::Watched),
+            bookmarks: LookupMap::new(StorageKey::Bookmarks),
+            curated_lists: LookupMap::new(StorageKey::CuratedLists),
+            curated_list_names: UnorderedSet::new(StorageKey::CuratedListNames),
+            pending_reverifications: UnorderedMap::new(StorageKey::PendingReverifications),
+            admin_log: Vector::new(StorageKey::AdminLog),
+            legacy_contracts: None,
+            badges: LookupMap::new(StorageKey::Badges),
+            tombstones: LookupMap::new(StorageKey::Tombstones),
+            bounty_deposits: LookupMap::new(StorageKey::BountyDeposits),
         }
     }
 
-    pub fn set_owner(&mut self, owner_id: AccountId) {
-        require!(env::predecessor_account_id() == self.owner_id, "Only owner can call this method");
-
-        self.owner_id = owner_id;
+    #[handle_result]
+    pub fn set_owner(&mut self, owner_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        self.check_not_timelocked()?;
 
-        log!("Owner changed to {}", self.owner_id)
+        self.change_owner(owner_id);
+        Ok(())
     }
 
     pub fn get_owner(&self) -> AccountId {
         return self.owner_id.clone();
     }
 
-    pub fn set_contract(&mut self, account_id: AccountId, cid: String, code_hash: String, lang: String, entry_point: String, builder_image: String, github: Option<GithubData>) {
-        require!(env::predecessor_account_id() == self.owner_id, "Only owner can call this method");
-
-        self.contracts.insert(&account_id, &ContractData {
-            cid: cid,
-            code_hash: code_hash,
-            lang: lang,
-            entry_point: entry_point,
-            builder_image: builder_image,
-            github: match github {
-                Some(github_data) => Some(GithubData {
-                    owner: github_data.owner.clone(),
-                    repo: github_data.repo.clone(),
-                    sha: github_data.sha.clone(),
-                }),
-                None => None,
-            },
+    /// Verifies `account_id`. When this mints a verification badge, attach its storage
+    /// deposit; any excess is refunded. An entry left `Pending` gets its badge and pays
+    /// out its bounty once `attest_contract` verifies it.
+    #[payable]
+    #[handle_result]
+    pub fn set_contract(&mut self, account_id: AccountId, cid: String, code_hash: String, lang: Lang, build_targets: Vec<BuildTarget>, builder_image: String, source: Option<RepoSource>, build_info: Option<BuildInfo>, signature: Option<String>, network: Option<Network>) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_staked()?;
+        self.check_not_paused()?;
+        self.check_allowed_account(&account_id)?;
+        let code_hash = self.check_valid_code_hash(&code_hash)?;
+        self.check_valid_lang(&lang)?;
+        if let Some(network) = &network {
+            self.check_valid_network(network)?;
+        }
+        self.check_valid_build_targets(&build_targets)?;
+        self.check_allowed_builder_image(&builder_image)?;
+        if let Some(source) = &source {
+            self.check_valid_source(source)?;
+        }
+        if let Some(build_info) = &build_info {
+            self.check_valid_build_info(build_info, &builder_image)?;
+        }
+        let cid = normalize_cid(&cid)
+            .ok_or_else(|| SourceScanError::invalid_cid(&cid))?;
+
+        let attestation = signature
+            .map(|signature| self.attest(&account_id, &code_hash, &cid, signature))
+            .transpose()?;
+        self.charge_badge_deposit(self.verification_mints_badge(&account_id, &code_hash))?;
+
+        self.apply_verification(&account_id, code_hash, network, attestation, SourceRecord {
+            cid,
+            lang,
+            build_targets,
+            builder_image,
+            source,
+            build_info,
+        })
+    }
+
+    /// Removes the entry and everything attached to it. Its history is dropped too unless
+    /// `keep_history` is set.
+    #[handle_result]
+    pub fn purge_contract(&mut self, account_id: AccountId, keep_history: Option<bool>) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        self.check_not_timelocked()?;
+
+        self.purge(vec![account_id], keep_history.unwrap_or(false));
+        Ok(())
+    }
+
+    /// Purges several entries in one call, logging a single `contract_purged` event.
+    #[handle_result]
+    pub fn purge_contracts(&mut self, account_ids: Vec<AccountId>, keep_history: Option<bool>) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        self.check_not_timelocked()?;
+
+        self.purge(account_ids, keep_history.unwrap_or(false));
+        Ok(())
+    }
+
+    pub fn get_contract(&self, account_id: AccountId) -> Option<ContractData> {       
+        return self.view_contract(&account_id);
+    }
+
+    /// Entries for `account_ids`, in the same order; `None` where an account has no entry.
+    pub fn get_contracts_bulk(&self, account_ids: Vec<AccountId>) -> Vec<Option<ContractData>> {
+        account_ids
+            .iter()
+            .map(|account_id| self.view_contract(account_id))
+            .collect()
+    }
+
+    pub fn get_contracts(&self, from_index: usize, limit: usize, sort_by: Option<SortBy>) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        let limit = self.page_limit(limit);
+        let filtered: Vec<(AccountId, ContractData)> = match sort_by {
+            None => self.contracts
+            .iter()
+            .skip(from_index)
+            .take(limit)
+            .map(|(account_id, contract)| (account_id, self.expire(self.resolve_contract(contract))))
+            .collect(),
+            Some(sort_by) => self.sorted_account_ids(sort_by, from_index, limit)
+            .into_iter()
+            .filter_map(|account_id| {
+                let contract = self.view_contract(&account_id)?;
+                Some((account_id, contract))
+            })
+            .collect(),
+        };
+
+        let total_count = self.contracts.len();
+        let pages: u64 = self.get_pages(total_count, limit as u64);
+
+        return (filtered, pages, total_count);
+    }
+
+    /// Accounts with an entry, in storage order, without loading the entries.
+    pub fn get_account_ids(&self, from_index: usize, limit: usize) -> Vec<AccountId> {
+        self.contracts
+            .keys()
+            .skip(from_index)
+            .take(self.page_limit(limit))
+            .collect()
+    }
+
+    pub fn get_contract_history(&self, account_id: AccountId, from_index: usize, limit: usize) -> (Vec<ContractSnapshot>, u64, u64) {
+        let limit = self.page_limit(limit);
+        let Some(history) = self.history.get(&account_id) else {
+            return (Vec::new(), 0, 0);
+        };
+
+        let filtered: Vec<ContractSnapshot> = history
+            .iter()
+            .skip(from_index)
+            .take(limit)
+            .map(|record| ContractSnapshot {
+                contract: self.resolve_contract(record.contract),
+                timestamp: record.timestamp,
+                block_height: record.block_height,
+            })
+            .collect();
+
+        let pages: u64 = self.get_pages(history.len(), limit as u64);
+
+        (filtered, pages, history.len())
+    }
+
+    fn read_contract(&self, account_id: &AccountId) -> Option<ContractData> {
+        self.contracts.get(account_id).map(|contract| self.resolve_contract(contract))
+    }
+
+    /// Stores a new verification of `account_id` against `record`, keeping the creation
+    /// time of an existing entry, and its network unless `network` is given.
+    pub(crate) fn apply_verification(&mut self, account_id: &AccountId, code_hash: String, network: Option<Network>, attestation: Option<Attestation>, record: SourceRecord) -> Result<(), SourceScanError> {
+        let (previous, contract) = self.new_verification(account_id, code_hash, network, attestation, record);
+        let verified = contract.status == VerificationStatus::Verified;
+        let mints_badge = verified && self.mints_badge(account_id, &contract.code_hash);
+
+        self.check_consistent_source(account_id, &contract)?;
+        Event::ContractSet(vec![ContractSetData {
+            account_id: account_id.clone(),
+            code_hash: contract.code_hash.clone(),
+            cid: contract.cid.clone(),
+        }])
+        .emit();
+
+        self.store_contract(account_id, &contract);
+        self.index_contract(account_id, previous.as_ref(), &contract);
+        self.finish_verification(account_id, previous.as_ref(), &contract);
+        if mints_badge {
+            self.mint_badge(account_id, &contract.code_hash);
+        }
+        self.push_history(account_id, contract);
+        self.log_admin(AdminLogAction::SetContract {
+            account_id: account_id.clone(),
         });
+        if verified {
+            self.pay_bounty(account_id);
+        }
+        Ok(())
+    }
+
+    /// Builds the entry a verification of `account_id` stores, returned with the entry
+    /// it replaces. Risk flags carry over while the code hash stays the same.
+    pub(crate) fn new_verification(&self, account_id: &AccountId, code_hash: String, network: Option<Network>, attestation: Option<Attestation>, record: SourceRecord) -> (Option<ContractData>, ContractData) {
+        let previous = self.read_contract(account_id);
+        let now = U64(env::block_timestamp());
+        let same_code = previous.as_ref().is_some_and(|previous| previous.code_hash == code_hash);
+        let risk_flags = previous
+            .as_ref()
+            .filter(|_| same_code)
+            .and_then(|previous| previous.risk_flags);
+
+        let network = network
+            .or_else(|| previous.as_ref().map(|previous| previous.network.clone()))
+            .unwrap_or_default();
+        let status = self.attested_status(account_id, same_code);
+
+        let contract = ContractData::join(AccountEntry {
+            code_hash,
+            network,
+            created_at: previous.as_ref().map_or(now, |previous| previous.created_at),
+            updated_at: now,
+            verified_at_block_height: U64(env::block_height()),
+            verified_at_epoch: U64(env::epoch_height()),
+            status,
+            risk_flags,
+            attestation,
+            last_checked_at: None,
+            onchain_hash_at_check: None,
+        }, record);
 
-        log!("Contract {} added", env::predecessor_account_id());
+        (previous, contract)
     }
 
-    pub fn search(&self, key: String, from_index: usize, limit: usize) -> (Vec<(AccountId, ContractData)>, u64) {
-        let mut result: Vec<(AccountId, ContractData)> = Vec::new();
+    /// Per-account bookkeeping of a verification, once its entry is stored and indexed.
+    pub(crate) fn finish_verification(&mut self, account_id: &AccountId, previous: Option<&ContractData>, contract: &ContractData) {
+        let same_code = previous.is_some_and(|previous| previous.code_hash == contract.code_hash);
 
-        for (k, v) in self.contracts.iter()
-        {            
-            if k.as_str().to_lowercase().replace(".testnet", "").replace(".near", "").contains(&key.to_lowercase()) {
-                result.push((k, v));
-            }
+        if let Some(previous) = previous {
+            self.release_source(&previous.code_hash);
         }
-        
-        let pages: u64 = self.get_pages(result.len() as u64, limit as u64);
-        let filtered: Vec<(AccountId, ContractData)> = result
-        .into_iter()
-        .skip(from_index)
-        .take(limit)
-        .collect();
+        self.record_attester(account_id, same_code);
+        self.clear_reverification(account_id);
+        self.record_change(account_id, ChangeKind::Set);
+        if previous.is_some() {
+            self.notify_watchers(account_id, ChangeKind::Set);
+        }
+    }
 
-        return (filtered, pages);
+    fn change_owner(&mut self, owner_id: AccountId) {
+        let old_owner_id = std::mem::replace(&mut self.owner_id, owner_id);
+        self.log_admin(AdminLogAction::SetOwner {
+            owner_id: self.owner_id.clone(),
+        });
+
+        Event::OwnerChanged(vec![OwnerChangedData {
+            old_owner_id,
+            new_owner_id: self.owner_id.clone(),
+        }])
+        .emit();
     }
 
-    pub fn purge_contract(&mut self, account_id: AccountId) {
-        require!(env::predecessor_account_id() == self.owner_id, "Only owner can call this method");
+    fn purge(&mut self, account_ids: Vec<AccountId>, keep_history: bool) {
+        for account_id in &account_ids {
+            self.remove_legacy_entry(account_id);
+            self.remove_contract(account_id);
+            if !keep_history {
+                self.clear_history(account_id);
+            }
+        }
+        self.log_admin(AdminLogAction::PurgeContracts {
+            account_ids: account_ids.clone(),
+        });
 
-        self.contracts.remove(&account_id);
+        Event::ContractPurged(
+            account_ids
+                .into_iter()
+                .map(|account_id| ContractPurgedData { account_id })
+                .collect(),
+        )
+        .emit();
+    }
 
-        log!("Contract {} removed", account_id);
+    fn remove_contract(&mut self, account_id: &AccountId) {
+        if let Some(contract) = self.contracts.remove(account_id) {
+            let contract = self.resolve_contract(contract);
+            self.unindex_contract(account_id, &contract);
+            self.add_tombstone(account_id);
+            self.release_source(&contract.code_hash);
+            self.clear_tags(account_id);
+            self.clear_audits(account_id);
+            self.attesters.remove(account_id);
+            self.clear_claim(account_id);
+            self.clear_project_metadata(account_id);
+            self.remove_from_lists(account_id);
+            self.clear_reverification(account_id);
+            self.burn_badge(account_id);
+            self.record_change(account_id, ChangeKind::Purged);
+        }
     }
 
-    pub fn get_contract(&self, account_id: AccountId) -> Option<ContractData> {       
-        return self.contracts.get(&account_id);
+    fn clear_history(&mut self, account_id: &AccountId) {
+        if let Some(mut history) = self.history.remove(account_id) {
+            for record in history.iter() {
+                self.release_snapshot(&record.contract);
+            }
+            history.clear();
+        }
     }
 
-    pub fn get_contracts(&self, from_index: usize, limit: usize) -> (Vec<(AccountId, ContractData)>, u64) {
-        let filtered:Vec<(AccountId, ContractData)> = self.contracts
-        .iter()
-        .skip(from_index)
-        .take(limit)
-        .collect();
+    pub(crate) fn push_history(&mut self, account_id: &AccountId, contract: ContractData) {
+        let mut history = self.history.get(account_id).unwrap_or_else(|| {
+            Vector::new(StorageKey::ContractHistoryEntries {
+                account_hash: env::sha256_array(account_id.as_str().as_bytes()),
+            })
+        });
 
-        let pages: u64 = self.get_pages(self.contracts.len(), limit as u64);
+        history.push(&HistoryRecord {
+            contract: self.snapshot_contract(contract),
+            timestamp: env::block_timestamp().into(),
+            block_height: env::block_height().into(),
+        });
 
-        return (filtered, pages);
+        self.history.insert(account_id, &history);
     }
 
     fn get_pages (&self, len: u64, limit: u64) -> u64 {
         return (len + limit - 1) / limit;
     }
+
+    /// Validates the `limit` of a paged view and caps it at `Config::max_page_size`.
+    pub(crate) fn page_limit(&self, limit: usize) -> usize {
+        assert!(limit > 0, "limit must be greater than 0");
+
+        limit.min(self.config.max_page_size as usize)
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
     use near_sdk::{testing_env, AccountId};
 
     // Helper function to set up the testing environment
-    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+    pub(crate) fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
         builder
             .current_account_id(accounts(0))
@@ -156,6 +694,121 @@ mod tests {
         builder
     }
 
+    /// A well-formed CIDv0 derived from `seed`, in the canonical form `set_contract` stores.
+    pub(crate) fn cid(seed: &str) -> String {
+        let mut multihash = vec![0x12, 0x20];
+        multihash.extend(env::sha256_array(seed.as_bytes()));
+        normalize_cid(&near_sdk::bs58::encode(multihash).into_string()).unwrap()
+    }
+
+    /// A well-formed base58 code hash derived from `seed`.
+    pub(crate) fn code_hash(seed: &str) -> String {
+        near_sdk::bs58::encode(env::sha256_array(seed.as_bytes())).into_string()
+    }
+
+    /// `set_contract` arguments with default metadata, built by `entry`.
+    pub(crate) struct EntryBuilder {
+        account_id: AccountId,
+        cid: String,
+        code_hash: String,
+        lang: Lang,
+        build_targets: Vec<BuildTarget>,
+        builder_image: String,
+        source: Option<RepoSource>,
+        build_info: Option<BuildInfo>,
+        signature: Option<String>,
+        network: Option<Network>,
+    }
+
+    /// Starts a verification of `account_id` with the code hash derived from `code`.
+    pub(crate) fn entry(account_id: AccountId, code: &str) -> EntryBuilder {
+        EntryBuilder {
+            account_id,
+            cid: cid("cid"),
+            code_hash: code_hash(code),
+            lang: Lang::Rust,
+            build_targets: vec!["entry_point".to_string().into()],
+            builder_image: "builder_image".to_string(),
+            source: None,
+            build_info: None,
+            signature: None,
+            network: None,
+        }
+    }
+
+    impl EntryBuilder {
+        /// Passed as is, so tests can submit CIDs that are not canonical or invalid.
+        pub(crate) fn cid(mut self, cid: impl Into<String>) -> Self {
+            self.cid = cid.into();
+            self
+        }
+
+        /// Passed as is, so tests can submit code hashes that are not canonical or invalid.
+        pub(crate) fn code_hash(mut self, code_hash: impl Into<String>) -> Self {
+            self.code_hash = code_hash.into();
+            self
+        }
+
+        pub(crate) fn lang(mut self, lang: Lang) -> Self {
+            self.lang = lang;
+            self
+        }
+
+        pub(crate) fn build_targets(mut self, build_targets: Vec<BuildTarget>) -> Self {
+            self.build_targets = build_targets;
+            self
+        }
+
+        pub(crate) fn image(mut self, builder_image: &str) -> Self {
+            self.builder_image = builder_image.to_string();
+            self
+        }
+
+        pub(crate) fn source(mut self, source: impl Into<Option<RepoSource>>) -> Self {
+            self.source = source.into();
+            self
+        }
+
+        pub(crate) fn build_info(mut self, build_info: BuildInfo) -> Self {
+            self.build_info = Some(build_info);
+            self
+        }
+
+        pub(crate) fn signature(mut self, signature: &str) -> Self {
+            self.signature = Some(signature.to_string());
+            self
+        }
+
+        pub(crate) fn network(mut self, network: impl Into<Option<Network>>) -> Self {
+            self.network = network.into();
+            self
+        }
+
+        pub(crate) fn try_set(self, contract: &mut SourceScan) -> Result<(), SourceScanError> {
+            contract.set_contract(
+                self.account_id,
+                self.cid,
+                self.code_hash,
+                self.lang,
+                self.build_targets,
+                self.builder_image,
+                self.source,
+                self.build_info,
+                self.signature,
+                self.network
+            )
+        }
+
+        pub(crate) fn set(self, contract: &mut SourceScan) {
+            self.try_set(contract).unwrap();
+        }
+    }
+
+    /// Verifies `account_id` with default metadata and the code hash derived from `code`.
+    pub(crate) fn set_entry(contract: &mut SourceScan, account_id: AccountId, code: &str) {
+        entry(account_id, code).set(contract);
+    }
+
     #[test]
     #[should_panic(expected = "SourceScan should be initialized before usage")]
     fn default_constructor() {
@@ -181,19 +834,21 @@ mod tests {
         testing_env!(context.build());
 
         let mut contract = SourceScan::new();
-        contract.set_owner(accounts(1));
+        contract.set_owner(accounts(1)).unwrap();
         assert_eq!(contract.get_owner(), accounts(1));
     }
 
     #[test]
-    #[should_panic(expected = "Only owner can call this method")]
     fn set_owner_unauthorized() {
         let context = get_context(accounts(1));
         testing_env!(context.build());
 
         let mut contract = SourceScan::new();
-        contract.set_owner(accounts(2));
-        contract.set_owner(accounts(3)); // This should panic
+        contract.set_owner(accounts(2)).unwrap();
+        assert_eq!(
+            contract.set_owner(accounts(3)),
+            Err(SourceScanError::Unauthorized("Only owner can call this method".to_string()))
+        );
     }
 
     #[test]
@@ -202,29 +857,25 @@ mod tests {
         testing_env!(context.build());
 
         let mut contract = SourceScan::new();
-        let github_data = GithubData {
+        let source = RepoSource::Github(RepoData {
             owner: "owner".to_string(),
             repo: "repo".to_string(),
-            sha: "sha".to_string(),
-        };
+            sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            path: Some("contracts/registry".to_string()),
+            git_ref: Some("main".to_string()),
+        });
 
-        contract.set_contract(
-            accounts(1), 
-            "cid".to_string(), 
-            "code_hash".to_string(), 
-            "lang".to_string(), 
-            "entry_point".to_string(), 
-            "builder_image".to_string(), 
-            Some(github_data)
-        );
+        entry(accounts(1), "code_hash").source(source).set(&mut contract);
 
         let contract_data = contract.get_contract(accounts(1)).unwrap();
-        assert_eq!(contract_data.cid, "cid");
-        assert_eq!(contract_data.code_hash, "code_hash");
-        assert_eq!(contract_data.lang, "lang");
-        assert_eq!(contract_data.entry_point, "entry_point");
+        assert_eq!(contract_data.cid, cid("cid"));
+        assert_eq!(contract_data.code_hash, code_hash("code_hash"));
+        assert_eq!(contract_data.lang, Lang::Rust);
+        assert_eq!(contract_data.build_targets, vec![BuildTarget::from("entry_point".to_string())]);
         assert_eq!(contract_data.builder_image, "builder_image");
-        assert!(contract_data.github.is_some());
+        let source = contract_data.source.unwrap();
+        assert_eq!(source.path(), Some("contracts/registry"));
+        assert_eq!(source.git_ref(), Some("main"));
     }
 
     #[test]
@@ -235,37 +886,53 @@ mod tests {
         let mut contract = SourceScan::new();
 
         // Setup: Add a contract
-        let github_data = GithubData {
+        let source = RepoSource::Github(RepoData {
             owner: "owner".to_string(),
             repo: "repo".to_string(),
-            sha: "sha".to_string(),
-        };
-        contract.set_contract(
-            accounts(1), 
-            "cid".to_string(), 
-            "code_hash".to_string(), 
-            "lang".to_string(), 
-            "entry_point".to_string(), 
-            "builder_image".to_string(), 
-            Some(github_data)
-        );
+            sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            path: None,
+            git_ref: None,
+        });
+        entry(accounts(1), "code_hash").source(source).set(&mut contract);
 
         // Action: Purge the contract
-        contract.purge_contract(accounts(1));
+        contract.purge_contract(accounts(1), None).unwrap();
 
         // Verification: Ensure contract is removed
         assert!(contract.get_contract(accounts(1)).is_none());
     }
 
     #[test]
-    #[should_panic(expected = "Only owner can call this method")]
+    fn purge_drops_history_unless_kept() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        for i in 1..3 {
+            entry(accounts(i), "code_hash").set(&mut contract);
+        }
+
+        contract.purge_contract(accounts(1), None).unwrap();
+        contract.purge_contract(accounts(2), Some(true)).unwrap();
+
+        let (history, pages, _) = contract.get_contract_history(accounts(1), 0, 10);
+        assert!(history.is_empty());
+        assert_eq!(pages, 0);
+        let (history, _, _) = contract.get_contract_history(accounts(2), 0, 10);
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
     fn purge_contract_unauthorized() {
         let context = get_context(accounts(1));
         testing_env!(context.build());
 
         let mut contract = SourceScan::new();
-        contract.set_owner(accounts(2));
-        contract.purge_contract(accounts(2));
+        contract.set_owner(accounts(2)).unwrap();
+        assert_eq!(
+            contract.purge_contract(accounts(2), None),
+            Err(SourceScanError::Unauthorized("Only owner or verifier can call this method".to_string()))
+        );
     }
 
     #[test]
@@ -276,23 +943,59 @@ mod tests {
         let mut contract = SourceScan::new();
         // Setup: Add multiple contracts
         for i in 1..4 {
-            contract.set_contract(
-                accounts(i), 
-                format!("cid_{}", i), 
-                "code_hash".to_string(), 
-                "lang".to_string(), 
-                "entry_point".to_string(), 
-                "builder_image".to_string(), 
-                None
-            );
+            entry(accounts(i), &format!("code_hash_{}", i))
+                .cid(cid(&format!("cid_{}", i)))
+                .set(&mut contract);
         }
 
         // Action: Retrieve contracts
-        let (contracts, total_pages) = contract.get_contracts(0, 2);
+        let (contracts, total_pages, total_count) = contract.get_contracts(0, 2, None);
 
         // Verification: Check the retrieved contracts and pagination
         assert_eq!(contracts.len(), 2);
         assert_eq!(total_pages, 2); // As we have 3 contracts and limit is 2
+        assert_eq!(total_count, 3);
+    }
+
+    #[test]
+    fn get_contracts_caps_limit() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        contract.set_config(Config { max_page_size: 2, ..Config::default() }).unwrap();
+        for i in 1..4 {
+            entry(accounts(i), "code_hash").set(&mut contract);
+        }
+
+        let (contracts, total_pages, total_count) = contract.get_contracts(0, 100, None);
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(total_pages, 2);
+        assert_eq!(total_count, 3);
+    }
+
+    #[test]
+    fn get_account_ids_pages_keys() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        for i in 1..4 {
+            entry(accounts(i), "code_hash").set(&mut contract);
+        }
+
+        assert_eq!(contract.get_account_ids(0, 2), vec![accounts(1), accounts(2)]);
+        assert_eq!(contract.get_account_ids(2, 2), vec![accounts(3)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "limit must be greater than 0")]
+    fn get_contracts_rejects_zero_limit() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = SourceScan::new();
+        contract.get_contracts(0, 0, None);
     }
 
     #[test]
@@ -302,31 +1005,142 @@ mod tests {
 
         let mut contract = SourceScan::new();
         // Setup: Add contracts with varying account_ids
-        contract.set_contract(
-            "account1.testnet".parse().unwrap(), 
-            "cid1".to_string(), 
-            "code_hash1".to_string(), 
-            "lang1".to_string(), 
-            "entry_point1".to_string(), 
-            "builder_image1".to_string(), 
-            None
-        );
-        contract.set_contract(
-            "account2.testnet".parse().unwrap(), 
-            "cid2".to_string(), 
-            "code_hash2".to_string(), 
-            "lang2".to_string(), 
-            "entry_point2".to_string(), 
-            "builder_image2".to_string(), 
-            None
-        );
+        entry("account1.testnet".parse().unwrap(), "code_hash1")
+            .cid(cid("cid1"))
+            .build_targets(vec!["entry_point1".to_string().into()])
+            .image("builder_image1")
+            .set(&mut contract);
+        entry("account2.testnet".parse().unwrap(), "code_hash2")
+            .cid(cid("cid2"))
+            .lang(Lang::Go)
+            .build_targets(vec!["entry_point2".to_string().into()])
+            .image("builder_image2")
+            .set(&mut contract);
 
         // Action: Search for contracts
-        let (search_results, _) = contract.search("account1".to_string(), 0, 10);
+        let search_results = contract.search("account1".to_string(), None, 10, None, None).results;
 
         // Verification: Check if the correct contract is retrieved
         assert_eq!(search_results.len(), 1);
         assert_eq!(search_results[0].0, "account1.testnet");
-        assert_eq!(search_results[0].1.cid, "cid1");
+        assert_eq!(search_results[0].1.cid, cid("cid1"));
+    }
+
+    #[test]
+    fn set_contract_emits_event() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "code_hash").set(&mut contract);
+
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let event: near_sdk::serde_json::Value =
+            near_sdk::serde_json::from_str(logs[0].strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+        assert_eq!(event["standard"], "sourcescan");
+        assert_eq!(event["event"], "contract_set");
+        assert_eq!(event["data"][0]["account_id"], "bob");
+        assert_eq!(event["data"][0]["code_hash"], code_hash("code_hash"));
+    }
+
+    #[test]
+    fn purge_contract_emits_event() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        contract.purge_contract(accounts(1), None).unwrap();
+
+        let logs = get_logs();
+        assert_eq!(
+            logs[0],
+            r#"EVENT_JSON:{"standard":"sourcescan","version":"1.0.0","event":"contract_purged","data":[{"account_id":"bob"}]}"#
+        );
+    }
+
+    #[test]
+    fn contract_history_keeps_previous_verifications() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.block_timestamp(100).block_index(1).build());
+
+        let mut contract = SourceScan::new();
+        for (source, hash) in [("cid1", "hash1"), ("cid2", "hash2"), ("cid3", "hash3")] {
+            entry(accounts(1), hash).cid(cid(source)).set(&mut contract);
+        }
+
+        let (history, pages, _) = contract.get_contract_history(accounts(1), 0, 2);
+        assert_eq!(pages, 2);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].contract.code_hash, code_hash("hash1"));
+        assert_eq!(history[0].timestamp, U64(100));
+        assert_eq!(history[0].block_height, U64(1));
+
+        let (history, _, _) = contract.get_contract_history(accounts(1), 2, 2);
+        assert_eq!(history[0].contract.cid, cid("cid3"));
+        assert_eq!(contract.get_contract(accounts(1)).unwrap().cid, cid("cid3"));
+
+        let (history, pages, _) = contract.get_contract_history(accounts(2), 0, 2);
+        assert!(history.is_empty());
+        assert_eq!(pages, 0);
+    }
+
+    #[test]
+    fn set_contract_records_timestamps() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.block_timestamp(100).block_index(1).build());
+
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "code_hash1").cid(cid("cid1")).set(&mut contract);
+
+        testing_env!(context.block_timestamp(200).block_index(5).build());
+        entry(accounts(1), "code_hash2").cid(cid("cid2")).set(&mut contract);
+
+        let contract_data = contract.get_contract(accounts(1)).unwrap();
+        assert_eq!(contract_data.created_at, U64(100));
+        assert_eq!(contract_data.updated_at, U64(200));
+        assert_eq!(contract_data.verified_at_block_height, U64(5));
+    }
+
+    #[test]
+    fn purge_contracts_in_batch() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        for i in 1..4 {
+            entry(accounts(i), "code_hash").set(&mut contract);
+        }
+
+        contract.purge_contracts(vec![accounts(1), accounts(3)], None).unwrap();
+
+        assert!(contract.get_contract(accounts(1)).is_none());
+        assert!(contract.get_contract(accounts(2)).is_some());
+        assert!(contract.get_contract(accounts(3)).is_none());
+        let (entries, _, total) = contract.get_contracts_by_code_hash(code_hash("code_hash"), 0, 10);
+        assert_eq!((entries[0].0.clone(), total), (accounts(2), 1));
+
+        let logs = get_logs();
+        assert_eq!(
+            logs.last().unwrap(),
+            r#"EVENT_JSON:{"standard":"sourcescan","version":"1.0.0","event":"contract_purged","data":[{"account_id":"bob"},{"account_id":"danny"}]}"#
+        );
+    }
+
+    #[test]
+    fn get_contracts_bulk_keeps_order() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = SourceScan::new();
+        for i in [1, 3] {
+            entry(accounts(i), accounts(i).as_str()).set(&mut contract);
+        }
+
+        let contracts = contract.get_contracts_bulk(vec![accounts(3), accounts(2), accounts(1)]);
+        assert_eq!(contracts.len(), 3);
+        assert_eq!(contracts[0].as_ref().unwrap().code_hash, code_hash(accounts(3).as_str()));
+        assert!(contracts[1].is_none());
+        assert_eq!(contracts[2].as_ref().unwrap().code_hash, code_hash(accounts(1).as_str()));
     }
 }
@@ -0,0 +1,241 @@
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::indexes::{index_insert, index_remove, key_hash};
+use crate::{AdminLogAction, ContractData, SourceScan, StorageKey};
+
+const MAX_LIST_NAME_LEN: usize = 64;
+
+#[near_bindgen]
+impl SourceScan {
+    /// Creates an empty curated list, e.g. "Audited DeFi", for the homepage.
+    #[handle_result]
+    pub fn create_list(&mut self, name: String) -> Result<(), SourceScanError> {
+        self.check_moderator()?;
+        self.check_not_paused()?;
+        let name = name.trim().to_string();
+        ensure!(
+            !name.is_empty(),
+            SourceScanError::InvalidInput("List name is required".to_string())
+        );
+        ensure!(
+            name.len() <= MAX_LIST_NAME_LEN,
+            SourceScanError::ContentTooLong(format!(
+                "List name must be at most {} bytes",
+                MAX_LIST_NAME_LEN
+            ))
+        );
+        ensure!(
+            self.curated_list_names.insert(&name),
+            SourceScanError::AlreadyExists(format!("List {:?} already exists", name))
+        );
+        self.log_admin(AdminLogAction::CreateList { name });
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn delete_list(&mut self, name: String) -> Result<(), SourceScanError> {
+        self.check_moderator()?;
+        self.check_not_paused()?;
+        ensure!(
+            self.curated_list_names.remove(&name),
+            SourceScanError::NotFound(format!("List {:?} not found", name))
+        );
+
+        if let Some(mut accounts) = self.curated_lists.remove(&name) {
+            accounts.clear();
+        }
+        self.log_admin(AdminLogAction::DeleteList { name });
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn add_to_list(
+        &mut self,
+        name: String,
+        account_ids: Vec<AccountId>,
+    ) -> Result<(), SourceScanError> {
+        self.check_moderator()?;
+        self.check_not_paused()?;
+        self.check_list_exists(&name)?;
+
+        for account_id in &account_ids {
+            ensure!(
+                self.contracts.get(account_id).is_some(),
+                SourceScanError::no_contract(account_id)
+            );
+            index_insert(&mut self.curated_lists, &name, account_id, || {
+                StorageKey::CuratedListEntries {
+                    name: key_hash(&name),
+                }
+            });
+        }
+        self.log_admin(AdminLogAction::AddToList { name, account_ids });
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn remove_from_list(
+        &mut self,
+        name: String,
+        account_ids: Vec<AccountId>,
+    ) -> Result<(), SourceScanError> {
+        self.check_moderator()?;
+        self.check_not_paused()?;
+        self.check_list_exists(&name)?;
+
+        for account_id in &account_ids {
+            index_remove(&mut self.curated_lists, &name, account_id);
+        }
+        self.log_admin(AdminLogAction::RemoveFromList { name, account_ids });
+        Ok(())
+    }
+
+    /// Entries on the list `name`, with the number of pages and of entries on it.
+    pub fn get_list(
+        &self,
+        name: String,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        self.page_index(&self.curated_lists, &name, from_index, limit)
+    }
+
+    /// Every curated list with its number of entries.
+    pub fn get_lists(&self) -> Vec<(String, u64)> {
+        self.curated_list_names
+            .iter()
+            .map(|name| {
+                let len = self
+                    .curated_lists
+                    .get(&name)
+                    .map_or(0, |accounts| accounts.len());
+                (name, len)
+            })
+            .collect()
+    }
+}
+
+impl SourceScan {
+    fn check_list_exists(&self, name: &String) -> Result<(), SourceScanError> {
+        ensure!(
+            self.curated_list_names.contains(name),
+            SourceScanError::NotFound(format!("List {:?} not found", name))
+        );
+        Ok(())
+    }
+
+    /// Takes a purged entry off every curated list.
+    pub(crate) fn remove_from_lists(&mut self, account_id: &AccountId) {
+        for name in self.curated_list_names.to_vec() {
+            index_remove(&mut self.curated_lists, &name, account_id);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn with_entries() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        for account_id in [accounts(1), accounts(2), accounts(3)] {
+            set_entry(&mut contract, account_id.clone(), account_id.as_str());
+        }
+        contract.add_moderator(accounts(4)).unwrap();
+        contract
+    }
+
+    #[test]
+    fn moderator_curates_lists() {
+        let mut contract = with_entries();
+
+        testing_env!(get_context(accounts(4)).build());
+        contract.create_list(" Audited DeFi ".to_string()).unwrap();
+        contract.create_list("Core".to_string()).unwrap();
+        contract
+            .add_to_list(
+                "Audited DeFi".to_string(),
+                vec![accounts(1), accounts(2), accounts(3)],
+            )
+            .unwrap();
+        contract
+            .remove_from_list("Audited DeFi".to_string(), vec![accounts(2)])
+            .unwrap();
+
+        let (entries, pages, total_count) = contract.get_list("Audited DeFi".to_string(), 0, 10);
+        assert_eq!(
+            entries
+                .into_iter()
+                .map(|(account_id, _)| account_id)
+                .collect::<Vec<_>>(),
+            vec![accounts(1), accounts(3)]
+        );
+        assert_eq!((pages, total_count), (1, 2));
+        assert_eq!(
+            contract.get_lists(),
+            vec![("Audited DeFi".to_string(), 2), ("Core".to_string(), 0)]
+        );
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.purge_contract(accounts(1), None).unwrap();
+        assert_eq!(contract.get_list("Audited DeFi".to_string(), 0, 10).2, 1);
+
+        contract.delete_list("Audited DeFi".to_string()).unwrap();
+        assert_eq!(contract.get_lists(), vec![("Core".to_string(), 0)]);
+        let log = contract.get_admin_log(0, 100);
+        assert_eq!(
+            log.iter()
+                .filter(|entry| entry.actor == accounts(4))
+                .map(|entry| entry.action.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                AdminLogAction::CreateList {
+                    name: "Audited DeFi".to_string()
+                },
+                AdminLogAction::CreateList {
+                    name: "Core".to_string()
+                },
+                AdminLogAction::AddToList {
+                    name: "Audited DeFi".to_string(),
+                    account_ids: vec![accounts(1), accounts(2), accounts(3)],
+                },
+                AdminLogAction::RemoveFromList {
+                    name: "Audited DeFi".to_string(),
+                    account_ids: vec![accounts(2)],
+                },
+            ]
+        );
+        assert_eq!(
+            log.last().unwrap().action,
+            AdminLogAction::DeleteList {
+                name: "Audited DeFi".to_string()
+            }
+        );
+        assert_eq!(
+            contract.add_to_list("Audited DeFi".to_string(), vec![accounts(3)]),
+            Err(SourceScanError::NotFound(
+                "List \"Audited DeFi\" not found".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn verifier_cannot_curate() {
+        let mut contract = with_entries();
+        contract.add_verifier(accounts(5)).unwrap();
+
+        testing_env!(get_context(accounts(5)).build());
+        assert_eq!(
+            contract.create_list("Core".to_string()),
+            Err(SourceScanError::Unauthorized(
+                "Only owner or moderator can call this method".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,479 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedMap;
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId, Gas, NearToken, Promise};
+
+use crate::cid::normalize_cid;
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, MigrationRejectedData};
+use crate::{
+    AdminLogAction, ChangeKind, ContractData, Network, RepoData, RepoSource, SourceScan,
+    VerificationStatus,
+};
+
+/// Storage key near-sdk keeps the contract struct under.
+const STATE_KEY: &[u8] = b"STATE";
+/// Storage key of the code waiting for `deploy_staged`, kept outside the contract struct
+/// so the blob is not read on every call.
+const STAGED_CODE_KEY: &[u8] = b"STAGED_CODE";
+/// Gas reserved for the `migrate` call that follows a deploy in `upgrade`.
+const MIGRATE_GAS: Gas = Gas::from_tgas(100);
+/// Gas a call to `deploy_staged` needs: reading and hashing the staged code, plus the
+/// `migrate` it schedules.
+pub(crate) const DEPLOY_STAGED_GAS: Gas = Gas::from_tgas(150);
+
+/// Source reference of `ContractDataV0`; always a GitHub repository.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub(crate) struct GithubDataV0 {
+    pub owner: String,
+    pub repo: String,
+    pub sha: String,
+}
+
+/// Entry layout written before `ContractData` was versioned.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub(crate) struct ContractDataV0 {
+    pub cid: String,
+    pub lang: String,
+    pub entry_point: String,
+    pub code_hash: String,
+    pub builder_image: String,
+    pub github: Option<GithubDataV0>,
+}
+
+impl From<ContractDataV0> for ContractData {
+    fn from(contract: ContractDataV0) -> Self {
+        Self {
+            cid: contract.cid,
+            lang: contract.lang.into(),
+            build_targets: vec![contract.entry_point.into()],
+            code_hash: contract.code_hash,
+            builder_image: contract.builder_image,
+            source: contract.github.map(|github| {
+                RepoSource::Github(RepoData {
+                    owner: github.owner,
+                    repo: github.repo,
+                    sha: github.sha,
+                    path: None,
+                    git_ref: None,
+                })
+            }),
+            build_info: None,
+            network: Network::Mainnet,
+            // The original verification time was never recorded.
+            created_at: U64(0),
+            updated_at: U64(0),
+            verified_at_block_height: U64(0),
+            verified_at_epoch: U64(0),
+            status: VerificationStatus::Verified,
+            risk_flags: None,
+            attestation: None,
+            last_checked_at: None,
+            onchain_hash_at_check: None,
+        }
+    }
+}
+
+/// State layout of the first deployed version of the registry.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub(crate) struct SourceScanV0 {
+    pub owner_id: AccountId,
+    pub contracts: UnorderedMap<AccountId, ContractDataV0>,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Upgrades state written by the first deployed version to the current layout.
+    /// Only the top-level state is rewritten here, so the call fits in the gas `upgrade`
+    /// attaches however many entries exist; the entries themselves are moved by
+    /// `migrate_batch`, and the registry stays paused until they all are. State that
+    /// already has the current layout is kept as is, so `upgrade` can always call this.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        // Only dropped here, so code staged for a deploy whose migration fails is kept.
+        env::storage_remove(STAGED_CODE_KEY);
+        let state = env::storage_read(STATE_KEY).expect("Failed to read contract state");
+        if let Ok(current) = Self::try_from_slice(&state) {
+            return current;
+        }
+
+        let old = SourceScanV0::try_from_slice(&state).expect("Failed to read contract state");
+        let mut contract = Self::with_owner(old.owner_id);
+        if !old.contracts.is_empty() {
+            contract.legacy_contracts = Some(old.contracts);
+            contract.paused = true;
+        }
+
+        contract
+    }
+
+    /// Moves up to `limit` entries left by `migrate` into the current layout, rebuilding
+    /// their indexes, and returns how many are left. Call it until it returns 0, then
+    /// `unpause`.
+    ///
+    /// Entries whose code hash or CID does not validate, or whose sources differ from
+    /// the record another migrated account already shares, are dropped and reported in
+    /// a `migration_rejected` event so they can be verified again. Accounts the owner
+    /// verified again while the registry was paused keep that newer entry.
+    #[handle_result]
+    pub fn migrate_batch(&mut self, limit: u32) -> Result<u64, SourceScanError> {
+        self.check_owner()?;
+        ensure!(
+            limit > 0,
+            SourceScanError::InvalidInput("limit must be greater than 0".to_string())
+        );
+        let Some(mut legacy) = self.legacy_contracts.take() else {
+            return Ok(0);
+        };
+
+        let batch: Vec<(AccountId, ContractDataV0)> = legacy.iter().take(limit as usize).collect();
+        let migrated = batch.len() as u64;
+        let mut rejected = Vec::new();
+        for (account_id, entry) in batch {
+            legacy.remove(&account_id);
+            if let Err(error) = self.migrate_entry(&account_id, entry) {
+                rejected.push(MigrationRejectedData {
+                    account_id,
+                    reason: error.to_string(),
+                });
+            }
+        }
+        if !rejected.is_empty() {
+            Event::MigrationRejected(rejected).emit();
+        }
+
+        let remaining = legacy.len();
+        if remaining > 0 {
+            self.legacy_contracts = Some(legacy);
+        }
+        self.log_admin(AdminLogAction::MigrateBatch {
+            migrated,
+            remaining,
+        });
+        Ok(remaining)
+    }
+
+    /// Number of entries still waiting for `migrate_batch`.
+    pub fn get_pending_migration_count(&self) -> u64 {
+        self.legacy_contracts
+            .as_ref()
+            .map_or(0, |legacy| legacy.len())
+    }
+
+    /// Deploys `code` over the registry and runs `migrate` in the same batch, so stored
+    /// verifications carry over. Must be called by the registry account itself with its
+    /// own key; under council governance use `stage_code` and `deploy_staged` instead.
+    #[private]
+    pub fn upgrade(&mut self, #[serializer(borsh)] code: Vec<u8>) -> Promise {
+        deploy_and_migrate(code)
+    }
+
+    /// Stores `code` for a later `deploy_staged` and returns its base58 sha256 hash.
+    /// Proposal arguments are JSON, so a council cannot pass wasm to `upgrade` directly.
+    /// Owner and council members may stage; deploying still takes the registry itself.
+    #[handle_result]
+    pub fn stage_code(
+        &mut self,
+        #[serializer(borsh)] code: Vec<u8>,
+    ) -> Result<String, SourceScanError> {
+        let caller = env::predecessor_account_id();
+        ensure!(
+            caller == self.owner_id || self.council.members.contains(&caller),
+            SourceScanError::Unauthorized(
+                "Only owner or council members can stage code".to_string()
+            )
+        );
+
+        env::storage_write(STAGED_CODE_KEY, &code);
+        let code_hash = code_hash_of(&code);
+        self.log_admin(AdminLogAction::StageCode {
+            code_hash: code_hash.clone(),
+        });
+        Ok(code_hash)
+    }
+
+    /// Deploys the staged code and runs `migrate` in the same batch. `code_hash` must be
+    /// the hash `stage_code` returned, so a proposal only deploys the code it was voted
+    /// on. A failing `migrate` rolls back the whole batch, deploy included, and keeps
+    /// the code staged; entries of the first version are moved later by
+    /// `migrate_batch`, so migration cannot run out of gas here however large the
+    /// registry is. Needs `DEPLOY_STAGED_GAS` of prepaid gas.
+    #[private]
+    #[handle_result]
+    pub fn deploy_staged(&mut self, code_hash: String) -> Result<Promise, SourceScanError> {
+        ensure!(
+            env::prepaid_gas() >= DEPLOY_STAGED_GAS,
+            SourceScanError::InvalidInput(format!(
+                "deploy_staged needs {} Tgas of prepaid gas",
+                DEPLOY_STAGED_GAS.as_tgas()
+            ))
+        );
+        let code_hash = self.check_valid_code_hash(&code_hash)?;
+        let code = env::storage_read(STAGED_CODE_KEY)
+            .ok_or_else(|| SourceScanError::NotFound("No code is staged".to_string()))?;
+        ensure!(
+            code_hash_of(&code) == code_hash,
+            SourceScanError::InvalidInput(format!(
+                "Staged code has hash {}, not {}",
+                code_hash_of(&code),
+                code_hash
+            ))
+        );
+
+        self.log_admin(AdminLogAction::DeployStaged { code_hash });
+        Ok(deploy_and_migrate(code))
+    }
+
+    /// Base58 sha256 hash of the staged code, if any.
+    pub fn get_staged_code_hash(&self) -> Option<String> {
+        env::storage_read(STAGED_CODE_KEY).map(|code| code_hash_of(&code))
+    }
+}
+
+fn code_hash_of(code: &[u8]) -> String {
+    near_sdk::bs58::encode(env::sha256_array(code)).into_string()
+}
+
+fn deploy_and_migrate(code: Vec<u8>) -> Promise {
+    Promise::new(env::current_account_id())
+        .deploy_contract(code)
+        .function_call(
+            "migrate".to_string(),
+            Vec::new(),
+            NearToken::from_yoctonear(0),
+            MIGRATE_GAS,
+        )
+}
+
+impl SourceScan {
+    /// Drops the entry `account_id` still has in the first version's layout, so a purge
+    /// during the migration is not undone by `migrate_batch`.
+    pub(crate) fn remove_legacy_entry(&mut self, account_id: &AccountId) {
+        if let Some(legacy) = self.legacy_contracts.as_mut() {
+            legacy.remove(account_id);
+        }
+    }
+
+    fn migrate_entry(
+        &mut self,
+        account_id: &AccountId,
+        entry: ContractDataV0,
+    ) -> Result<(), SourceScanError> {
+        if self.contracts.get(account_id).is_some() {
+            return Ok(());
+        }
+        let mut contract = ContractData::from(entry);
+        contract.code_hash = self.check_valid_code_hash(&contract.code_hash)?;
+        contract.cid = normalize_cid(&contract.cid)
+            .ok_or_else(|| SourceScanError::invalid_cid(&contract.cid))?;
+        self.check_consistent_source(account_id, &contract)?;
+
+        self.store_contract(account_id, &contract);
+        self.index_contract(account_id, None, &contract);
+        self.record_change(account_id, ChangeKind::Set);
+        self.push_history(account_id, contract);
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, code_hash, get_context};
+    use crate::StorageKey;
+    use near_sdk::test_utils::{accounts, get_logs};
+    use near_sdk::testing_env;
+
+    fn old_entry(cid: &str, code_hash: &str) -> ContractDataV0 {
+        ContractDataV0 {
+            cid: cid.to_string(),
+            lang: "Rust".to_string(),
+            entry_point: "entry_point".to_string(),
+            code_hash: code_hash.to_string(),
+            builder_image: "builder_image".to_string(),
+            github: None,
+        }
+    }
+
+    fn write_old_state(entries: &[(AccountId, ContractDataV0)]) {
+        let mut contracts = UnorderedMap::new(StorageKey::SourceScanRecords);
+        for (account_id, entry) in entries {
+            contracts.insert(account_id, entry);
+        }
+        env::state_write(&SourceScanV0 {
+            owner_id: accounts(3),
+            contracts,
+        });
+    }
+
+    #[test]
+    fn migrate_from_v0_state() {
+        testing_env!(get_context(accounts(0)).build());
+        let hex: String = env::sha256_array(b"hash2")
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        write_old_state(&[
+            (accounts(1), old_entry(&cid("cid"), &code_hash("hash1"))),
+            (accounts(2), old_entry(&cid("cid"), &hex)),
+        ]);
+
+        let mut contract = SourceScan::migrate();
+        assert_eq!(contract.get_owner(), accounts(3));
+        assert!(contract.is_paused());
+        assert_eq!(contract.get_pending_migration_count(), 2);
+        assert!(contract.get_contract(accounts(1)).is_none());
+
+        testing_env!(get_context(accounts(3)).build());
+        assert!(contract.unpause().is_err());
+        assert_eq!(contract.migrate_batch(1), Ok(1));
+        assert_eq!(contract.migrate_batch(1), Ok(0));
+        assert_eq!(contract.migrate_batch(1), Ok(0));
+        contract.unpause().unwrap();
+
+        let entry = contract.get_contract(accounts(1)).unwrap();
+        assert_eq!(entry.code_hash, code_hash("hash1"));
+        assert_eq!(entry.lang, crate::Lang::Rust);
+        let (entries, _, _) = contract.get_contracts_by_code_hash(code_hash("hash2"), 0, 10);
+        assert_eq!(entries[0].0, accounts(2));
+        let results = contract
+            .search("charlie".to_string(), None, 10, None, None)
+            .results;
+        assert_eq!(results[0].0, accounts(2));
+        let (history, _, _) = contract.get_contract_history(accounts(1), 0, 10);
+        assert_eq!(history.len(), 1);
+        assert_eq!(contract.get_changes_since(0, 10).len(), 2);
+
+        let old: UnorderedMap<AccountId, ContractDataV0> =
+            UnorderedMap::new(StorageKey::SourceScanRecords);
+        assert!(old.get(&accounts(1)).is_none());
+    }
+
+    #[test]
+    fn migrate_rejects_invalid_and_conflicting_entries() {
+        testing_env!(get_context(accounts(0)).build());
+        write_old_state(&[
+            (accounts(0), old_entry("cid", &code_hash("hash"))),
+            (accounts(1), old_entry(&cid("cid1"), &code_hash("factory"))),
+            (accounts(2), old_entry(&cid("cid2"), &code_hash("factory"))),
+        ]);
+
+        let mut contract = SourceScan::migrate();
+        testing_env!(get_context(accounts(3)).build());
+        assert_eq!(contract.migrate_batch(10), Ok(0));
+
+        assert!(contract.get_contract(accounts(0)).is_none());
+        assert!(contract.get_contract(accounts(1)).is_some());
+        assert!(contract.get_contract(accounts(2)).is_none());
+        let logs = get_logs();
+        assert!(logs[0].contains("migration_rejected"));
+        assert!(logs[0].contains("Invalid CID"));
+        assert!(logs[0].contains("its source record cannot change"));
+    }
+
+    #[test]
+    fn owner_changes_during_migration_are_kept() {
+        testing_env!(get_context(accounts(0)).build());
+        write_old_state(&[
+            (accounts(1), old_entry(&cid("cid"), &code_hash("old1"))),
+            (accounts(2), old_entry(&cid("cid"), &code_hash("old2"))),
+        ]);
+        let mut contract = SourceScan::migrate();
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.purge_contract(accounts(1), None).unwrap();
+        crate::tests::set_entry(&mut contract, accounts(2), "new2");
+        assert_eq!(contract.get_pending_migration_count(), 1);
+        assert_eq!(contract.migrate_batch(10), Ok(0));
+
+        assert!(contract.get_contract(accounts(1)).is_none());
+        assert_eq!(
+            contract.get_contract(accounts(2)).unwrap().code_hash,
+            code_hash("new2")
+        );
+        let (entries, _, _) = contract.get_contracts_by_code_hash(code_hash("old2"), 0, 10);
+        assert!(entries.is_empty());
+        assert_eq!(contract.get_stats().total_contracts, 1);
+    }
+
+    #[test]
+    fn migrated_entries_can_be_purged() {
+        testing_env!(get_context(accounts(0)).build());
+        write_old_state(&[(accounts(1), old_entry(&cid("cid"), &code_hash("old1")))]);
+        let mut contract = SourceScan::migrate();
+
+        testing_env!(get_context(accounts(3)).build());
+        assert_eq!(contract.migrate_batch(10), Ok(0));
+        contract.purge_contract(accounts(1), None).unwrap();
+        crate::tests::set_entry(&mut contract, accounts(1), "new1");
+
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().code_hash,
+            code_hash("new1")
+        );
+        assert_eq!(contract.get_stats().total_contracts, 1);
+    }
+
+    #[test]
+    fn migrate_keeps_current_state() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+        crate::tests::set_entry(&mut contract, accounts(2), "hash");
+        env::state_write(&contract);
+
+        let contract = SourceScan::migrate();
+
+        assert_eq!(contract.get_verifiers(), vec![accounts(1)]);
+        assert!(contract.get_contract(accounts(2)).is_some());
+        assert_eq!(contract.get_changes_since(0, 10).len(), 1);
+    }
+
+    #[test]
+    fn deploy_staged_checks_code_hash() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        let code = b"\0asm staged".to_vec();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert!(contract.stage_code(code.clone()).is_err());
+
+        testing_env!(get_context(accounts(0)).build());
+        assert!(contract.deploy_staged(code_hash("other")).is_err());
+        let hash = contract.stage_code(code).unwrap();
+        assert_eq!(contract.get_staged_code_hash(), Some(hash.clone()));
+
+        assert_eq!(
+            contract.deploy_staged(code_hash("other")).err(),
+            Some(SourceScanError::InvalidInput(format!(
+                "Staged code has hash {}, not {}",
+                hash,
+                code_hash("other")
+            )))
+        );
+        testing_env!(get_context(accounts(0))
+            .prepaid_gas(Gas::from_tgas(50))
+            .build());
+        assert_eq!(
+            contract.deploy_staged(hash.clone()).err(),
+            Some(SourceScanError::InvalidInput(
+                "deploy_staged needs 150 Tgas of prepaid gas".to_string()
+            ))
+        );
+
+        testing_env!(get_context(accounts(0))
+            .prepaid_gas(DEPLOY_STAGED_GAS)
+            .build());
+        assert!(contract.deploy_staged(hash).is_ok());
+        // Dropped by `migrate` only once the new code runs.
+        assert!(contract.get_staged_code_hash().is_some());
+        env::state_write(&contract);
+        SourceScan::migrate();
+        assert_eq!(contract.get_staged_code_hash(), None);
+    }
+}
@@ -0,0 +1,92 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::{ContractData, SourceScan};
+
+/// A standard implemented by a contract, as listed in NEP-330 metadata.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Standard {
+    pub standard: String,
+    pub version: String,
+}
+
+/// NEP-330 `ContractSourceMetadata` for a verified entry.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Nep330Metadata {
+    pub version: Option<String>,
+    pub link: Option<String>,
+    pub standards: Vec<Standard>,
+}
+
+impl From<&ContractData> for Nep330Metadata {
+    fn from(contract: &ContractData) -> Self {
+        let (version, link) = match &contract.source {
+            Some(source) => (Some(source.sha().to_string()), source.link()),
+            None => (None, format!("ipfs://{}", contract.cid)),
+        };
+
+        Self {
+            version,
+            link: Some(link),
+            standards: Vec::new(),
+        }
+    }
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Renders the stored entry for `account_id` as NEP-330 source metadata.
+    pub fn get_nep330_metadata(&self, account_id: AccountId) -> Option<Nep330Metadata> {
+        self.read_contract(&account_id)
+            .map(|contract| Nep330Metadata::from(&contract))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, entry, get_context};
+    use crate::{RepoData, RepoSource};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn nep330_metadata_links_to_source() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        entry(accounts(1), "code_hash")
+            .source(RepoSource::Github(RepoData {
+                owner: "near".to_string(),
+                repo: "core-contracts".to_string(),
+                sha: SHA.to_string(),
+                path: None,
+                git_ref: None,
+            }))
+            .set(&mut contract);
+        entry(accounts(2), "code_hash2")
+            .cid(cid("cid2"))
+            .set(&mut contract);
+
+        let metadata = contract.get_nep330_metadata(accounts(1)).unwrap();
+        assert_eq!(metadata.version, Some(SHA.to_string()));
+        assert_eq!(
+            metadata.link,
+            Some(format!(
+                "https://github.com/near/core-contracts/tree/{}",
+                SHA
+            ))
+        );
+
+        let metadata = contract.get_nep330_metadata(accounts(2)).unwrap();
+        assert_eq!(metadata.version, None);
+        assert_eq!(metadata.link, Some(format!("ipfs://{}", cid("cid2"))));
+
+        assert!(contract.get_nep330_metadata(accounts(3)).is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt;
+
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// Network the verified account is deployed on, so one registry can serve several
+/// environments.
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+    /// Any other environment, e.g. a localnet or sandbox, by its name.
+    Custom(String),
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Network::Mainnet => f.write_str("mainnet"),
+            Network::Testnet => f.write_str("testnet"),
+            Network::Custom(name) => write!(f, "custom:{}", name),
+        }
+    }
+}
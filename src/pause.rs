@@ -0,0 +1,132 @@
+use near_sdk::{env, near_bindgen};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, PauseData};
+use crate::{AdminLogAction, SourceScan};
+
+#[near_bindgen]
+impl SourceScan {
+    /// Freezes every non-admin mutation, e.g. while a compromised verifier key is rotated.
+    #[handle_result]
+    pub fn pause(&mut self) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        if !self.paused {
+            self.paused = true;
+            self.log_admin(AdminLogAction::Pause);
+            Event::Paused(vec![PauseData {
+                account_id: env::predecessor_account_id(),
+            }])
+            .emit();
+        }
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn unpause(&mut self) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        ensure!(
+            self.legacy_contracts.is_none(),
+            SourceScanError::InvalidState(
+                "Run migrate_batch until no entries are pending before unpausing".to_string()
+            )
+        );
+
+        if self.paused {
+            self.paused = false;
+            self.log_admin(AdminLogAction::Unpause);
+            Event::Unpaused(vec![PauseData {
+                account_id: env::predecessor_account_id(),
+            }])
+            .emit();
+        }
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl SourceScan {
+    /// The owner stays exempt so bad entries can still be cleaned up while paused.
+    pub(crate) fn check_not_paused(&self) -> Result<(), SourceScanError> {
+        ensure!(
+            !self.paused || env::predecessor_account_id() == self.owner_id,
+            SourceScanError::Paused
+        );
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{entry, get_context};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn paused_with_verifier() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+        contract.pause().unwrap();
+        contract
+    }
+
+    #[test]
+    fn owner_can_pause_and_unpause() {
+        let mut contract = paused_with_verifier();
+        assert!(contract.is_paused());
+
+        contract.purge_contract(accounts(2), None).unwrap();
+
+        contract.unpause().unwrap();
+        assert!(!contract.is_paused());
+        testing_env!(get_context(accounts(1)).build());
+        contract.purge_contract(accounts(2), None).unwrap();
+    }
+
+    #[test]
+    fn paused_registry_rejects_verifier_writes() {
+        let mut contract = paused_with_verifier();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            entry(accounts(2), "code_hash").try_set(&mut contract),
+            Err(SourceScanError::Paused)
+        );
+    }
+
+    #[test]
+    fn paused_registry_rejects_verifier_setup() {
+        let mut contract = paused_with_verifier();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.set_attestation_key(
+                "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                    .parse()
+                    .unwrap()
+            ),
+            Err(SourceScanError::Paused)
+        );
+
+        testing_env!(get_context(accounts(2)).build());
+        assert_eq!(contract.register_verifier(), Err(SourceScanError::Paused));
+    }
+
+    #[test]
+    fn verifier_cannot_unpause() {
+        let mut contract = paused_with_verifier();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.unpause(),
+            Err(SourceScanError::Unauthorized(
+                "Only owner can call this method".to_string()
+            ))
+        );
+    }
+}
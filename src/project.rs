@@ -0,0 +1,226 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::cid::normalize_cid;
+use crate::error::{ensure, SourceScanError};
+use crate::SourceScan;
+
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 280;
+const MAX_URL_LEN: usize = 256;
+const MAX_SOCIAL_LINKS: usize = 5;
+const MAX_LICENSE_LEN: usize = 64;
+
+/// Presentation details a claimed account sets for its own page. Every field is optional.
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ProjectMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub website: Option<String>,
+    pub docs: Option<String>,
+    #[serde(default)]
+    pub socials: Vec<String>,
+    /// IPFS CID of the logo, in canonical form.
+    pub logo_cid: Option<String>,
+    /// SPDX license expression, e.g. `MIT OR Apache-2.0`.
+    pub license: Option<String>,
+}
+
+fn check_len(field: &str, value: &str, max_len: usize) -> Result<(), SourceScanError> {
+    ensure!(
+        value.len() <= max_len,
+        SourceScanError::ContentTooLong(format!("{} must be at most {} bytes", field, max_len))
+    );
+    Ok(())
+}
+
+fn check_url(field: &str, url: &str) -> Result<(), SourceScanError> {
+    check_len(field, url, MAX_URL_LEN)?;
+    ensure!(
+        url.starts_with("https://") && !url.contains(char::is_whitespace),
+        SourceScanError::InvalidInput(format!("{} must be an https:// URL", field))
+    );
+    Ok(())
+}
+
+fn is_spdx_expression(license: &str) -> bool {
+    !license.trim().is_empty()
+        && license
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " .-+():".contains(c))
+}
+
+impl ProjectMetadata {
+    /// Checks the limits and canonicalizes the logo CID.
+    fn validated(mut self) -> Result<Self, SourceScanError> {
+        if let Some(name) = &self.name {
+            check_len("Name", name, MAX_NAME_LEN)?;
+        }
+        if let Some(description) = &self.description {
+            check_len("Description", description, MAX_DESCRIPTION_LEN)?;
+        }
+        if let Some(website) = &self.website {
+            check_url("Website", website)?;
+        }
+        if let Some(docs) = &self.docs {
+            check_url("Docs link", docs)?;
+        }
+        ensure!(
+            self.socials.len() <= MAX_SOCIAL_LINKS,
+            SourceScanError::ContentTooLong(format!(
+                "At most {} social links are allowed",
+                MAX_SOCIAL_LINKS
+            ))
+        );
+        for social in &self.socials {
+            check_url("Social link", social)?;
+        }
+        if let Some(license) = &self.license {
+            check_len("License", license, MAX_LICENSE_LEN)?;
+            ensure!(
+                is_spdx_expression(license),
+                SourceScanError::InvalidInput(format!(
+                    "Invalid license {:?}: expected an SPDX expression",
+                    license
+                ))
+            );
+        }
+        self.logo_cid = self
+            .logo_cid
+            .map(|cid| normalize_cid(&cid).ok_or_else(|| SourceScanError::invalid_cid(&cid)))
+            .transpose()?;
+
+        Ok(self)
+    }
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Replaces the project metadata of a claimed entry. Only the claimed account can call
+    /// this.
+    #[handle_result]
+    pub fn set_project_metadata(
+        &mut self,
+        account_id: AccountId,
+        metadata: ProjectMetadata,
+    ) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        self.check_maintainer(&account_id)?;
+
+        let metadata = metadata.validated()?;
+        self.project_metadata.insert(&account_id, &metadata);
+        Ok(())
+    }
+
+    pub fn get_project_metadata(&self, account_id: AccountId) -> Option<ProjectMetadata> {
+        self.project_metadata.get(&account_id)
+    }
+}
+
+impl SourceScan {
+    /// Drops the metadata of a purged entry.
+    pub(crate) fn clear_project_metadata(&mut self, account_id: &AccountId) {
+        self.project_metadata.remove(account_id);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn claimed() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.claim_contract(accounts(1)).unwrap();
+        contract
+    }
+
+    #[test]
+    fn maintainer_sets_metadata() {
+        let mut contract = claimed();
+
+        let metadata = ProjectMetadata {
+            name: Some("Ref Finance".to_string()),
+            website: Some("https://ref.finance".to_string()),
+            socials: vec!["https://x.com/finance_ref".to_string()],
+            logo_cid: Some(cid("logo")),
+            license: Some("MIT OR Apache-2.0".to_string()),
+            ..ProjectMetadata::default()
+        };
+        contract
+            .set_project_metadata(accounts(1), metadata.clone())
+            .unwrap();
+        assert_eq!(contract.get_project_metadata(accounts(1)), Some(metadata));
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.purge_contract(accounts(1), None).unwrap();
+        assert_eq!(contract.get_project_metadata(accounts(1)), None);
+    }
+
+    #[test]
+    fn metadata_is_validated() {
+        let mut contract = claimed();
+
+        assert_eq!(
+            contract.set_project_metadata(
+                accounts(1),
+                ProjectMetadata {
+                    website: Some("http://ref.finance".to_string()),
+                    ..ProjectMetadata::default()
+                }
+            ),
+            Err(SourceScanError::InvalidInput(
+                "Website must be an https:// URL".to_string()
+            ))
+        );
+        assert_eq!(
+            contract.set_project_metadata(
+                accounts(1),
+                ProjectMetadata {
+                    description: Some("a".repeat(MAX_DESCRIPTION_LEN + 1)),
+                    ..ProjectMetadata::default()
+                }
+            ),
+            Err(SourceScanError::ContentTooLong(
+                "Description must be at most 280 bytes".to_string()
+            ))
+        );
+        assert_eq!(
+            contract.set_project_metadata(
+                accounts(1),
+                ProjectMetadata {
+                    license: Some("MIT; rm -rf".to_string()),
+                    ..ProjectMetadata::default()
+                }
+            ),
+            Err(SourceScanError::InvalidInput(
+                "Invalid license \"MIT; rm -rf\": expected an SPDX expression".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn unclaimed_entry_cannot_set_metadata() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            contract.set_project_metadata(accounts(0), ProjectMetadata::default()),
+            Err(SourceScanError::Unauthorized(
+                "Only the account of a claimed entry can call this method".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,559 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{borsh, env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{ContractSetData, Event};
+use crate::validation::normalize_code_hash;
+use crate::{
+    AdminLogAction, Attestation, BuildInfo, BuildTarget, ContractData, Lang, Network, RepoSource,
+    RiskFlags, SourceScan, VerificationStatus, VersionedContractData,
+};
+
+/// Source metadata shared by every account deploying the same code, stored once per
+/// `code_hash`. It is fixed while more than one account references it.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct SourceRecord {
+    pub cid: String,
+    pub lang: Lang,
+    pub build_targets: Vec<BuildTarget>,
+    pub builder_image: String,
+    pub source: Option<RepoSource>,
+    pub build_info: Option<BuildInfo>,
+}
+
+/// A `SourceRecord` referenced by history snapshots, keyed by the hash of its contents
+/// so past snapshots keep their sources when the live record of the code hash changes.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct SnapshotSource {
+    pub record: SourceRecord,
+    /// Number of history snapshots referencing the record.
+    pub refs: u64,
+}
+
+/// The per-account part of `ContractData`; the rest lives in the `SourceRecord` of
+/// `code_hash`.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct AccountEntry {
+    pub code_hash: String,
+    pub network: Network,
+    pub created_at: U64,
+    pub updated_at: U64,
+    pub verified_at_block_height: U64,
+    pub verified_at_epoch: U64,
+    pub status: VerificationStatus,
+    pub risk_flags: Option<RiskFlags>,
+    pub attestation: Option<Attestation>,
+    pub last_checked_at: Option<U64>,
+    pub onchain_hash_at_check: Option<String>,
+}
+
+/// Layout of `ContractData` when `VersionedContractData::V1` was introduced, kept as
+/// is so V1 history snapshots and exported entries decode however `ContractData` changes.
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ContractDataV1 {
+    pub cid: String,
+    pub lang: Lang,
+    pub build_targets: Vec<BuildTarget>,
+    pub code_hash: String,
+    pub builder_image: String,
+    pub source: Option<RepoSource>,
+    pub build_info: Option<BuildInfo>,
+    pub network: Network,
+    pub created_at: U64,
+    pub updated_at: U64,
+    pub verified_at_block_height: U64,
+    pub verified_at_epoch: U64,
+    pub status: VerificationStatus,
+    pub risk_flags: Option<RiskFlags>,
+    pub attestation: Option<Attestation>,
+    pub last_checked_at: Option<U64>,
+    pub onchain_hash_at_check: Option<String>,
+}
+
+impl From<ContractDataV1> for ContractData {
+    fn from(contract: ContractDataV1) -> Self {
+        Self {
+            cid: contract.cid,
+            lang: contract.lang,
+            build_targets: contract.build_targets,
+            code_hash: contract.code_hash,
+            builder_image: contract.builder_image,
+            source: contract.source,
+            build_info: contract.build_info,
+            network: contract.network,
+            created_at: contract.created_at,
+            updated_at: contract.updated_at,
+            verified_at_block_height: contract.verified_at_block_height,
+            verified_at_epoch: contract.verified_at_epoch,
+            status: contract.status,
+            risk_flags: contract.risk_flags,
+            attestation: contract.attestation,
+            last_checked_at: contract.last_checked_at,
+            onchain_hash_at_check: contract.onchain_hash_at_check,
+        }
+    }
+}
+
+impl From<ContractData> for ContractDataV1 {
+    fn from(contract: ContractData) -> Self {
+        Self {
+            cid: contract.cid,
+            lang: contract.lang,
+            build_targets: contract.build_targets,
+            code_hash: contract.code_hash,
+            builder_image: contract.builder_image,
+            source: contract.source,
+            build_info: contract.build_info,
+            network: contract.network,
+            created_at: contract.created_at,
+            updated_at: contract.updated_at,
+            verified_at_block_height: contract.verified_at_block_height,
+            verified_at_epoch: contract.verified_at_epoch,
+            status: contract.status,
+            risk_flags: contract.risk_flags,
+            attestation: contract.attestation,
+            last_checked_at: contract.last_checked_at,
+            onchain_hash_at_check: contract.onchain_hash_at_check,
+        }
+    }
+}
+
+impl ContractData {
+    fn split(self) -> (AccountEntry, SourceRecord) {
+        (
+            AccountEntry {
+                code_hash: self.code_hash,
+                network: self.network,
+                created_at: self.created_at,
+                updated_at: self.updated_at,
+                verified_at_block_height: self.verified_at_block_height,
+                verified_at_epoch: self.verified_at_epoch,
+                status: self.status,
+                risk_flags: self.risk_flags,
+                attestation: self.attestation,
+                last_checked_at: self.last_checked_at,
+                onchain_hash_at_check: self.onchain_hash_at_check,
+            },
+            SourceRecord {
+                cid: self.cid,
+                lang: self.lang,
+                build_targets: self.build_targets,
+                builder_image: self.builder_image,
+                source: self.source,
+                build_info: self.build_info,
+            },
+        )
+    }
+
+    pub(crate) fn join(entry: AccountEntry, record: SourceRecord) -> Self {
+        Self {
+            cid: record.cid,
+            lang: record.lang,
+            build_targets: record.build_targets,
+            code_hash: entry.code_hash,
+            builder_image: record.builder_image,
+            source: record.source,
+            build_info: record.build_info,
+            network: entry.network,
+            created_at: entry.created_at,
+            updated_at: entry.updated_at,
+            verified_at_block_height: entry.verified_at_block_height,
+            verified_at_epoch: entry.verified_at_epoch,
+            status: entry.status,
+            risk_flags: entry.risk_flags,
+            attestation: entry.attestation,
+            last_checked_at: entry.last_checked_at,
+            onchain_hash_at_check: entry.onchain_hash_at_check,
+        }
+    }
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Verifies accounts a factory deployed with `code_hash` against the source record
+    /// an earlier `set_contract` stored for it, without resubmitting the metadata.
+    ///
+    /// Entries and their shared indexes are written in one pass, with one
+    /// `contract_set` event and one admin log entry for the whole call. Children get no
+    /// badge, history snapshot or bounty payout; verify a child with `set_contract` for
+    /// those.
+    #[handle_result]
+    pub fn set_contracts_for_factory(
+        &mut self,
+        code_hash: String,
+        account_ids: Vec<AccountId>,
+    ) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_staked()?;
+        self.check_not_paused()?;
+        ensure!(
+            !account_ids.is_empty(),
+            SourceScanError::InvalidInput("At least one account id is required".to_string())
+        );
+        ensure!(
+            account_ids.len() <= self.config.max_page_size as usize,
+            SourceScanError::ContentTooLong(format!(
+                "At most {} accounts can be set per call",
+                self.config.max_page_size
+            ))
+        );
+        let code_hash = self.check_valid_code_hash(&code_hash)?;
+        let record = self.sources.get(&code_hash).ok_or_else(|| {
+            SourceScanError::NotFound(format!("No source record for code hash {}", code_hash))
+        })?;
+        let mut unique = account_ids.clone();
+        unique.sort();
+        unique.dedup();
+        ensure!(
+            unique.len() == account_ids.len(),
+            SourceScanError::InvalidInput("Account ids must be unique".to_string())
+        );
+        for account_id in &account_ids {
+            self.check_allowed_account(account_id)?;
+        }
+
+        // Every child shares `record`, so none can conflict with it.
+        let mut entries = Vec::with_capacity(account_ids.len());
+        for account_id in &account_ids {
+            let (previous, contract) =
+                self.new_verification(account_id, code_hash.clone(), None, None, record.clone());
+            let (entry, _) = contract.clone().split();
+            self.contracts
+                .insert(account_id, &VersionedContractData::V2(entry));
+            entries.push((account_id.clone(), previous, contract));
+        }
+        self.index_contracts(&entries);
+        for (account_id, previous, contract) in &entries {
+            self.finish_verification(account_id, previous.as_ref(), contract);
+        }
+        self.log_admin(AdminLogAction::SetContractsForFactory {
+            code_hash: code_hash.clone(),
+            account_ids: account_ids.clone(),
+        });
+
+        Event::ContractSet(
+            account_ids
+                .into_iter()
+                .map(|account_id| ContractSetData {
+                    account_id,
+                    code_hash: code_hash.clone(),
+                    cid: record.cid.clone(),
+                })
+                .collect(),
+        )
+        .emit();
+        Ok(())
+    }
+
+    pub fn get_source_record(&self, code_hash: String) -> Option<SourceRecord> {
+        self.sources.get(&normalize_code_hash(&code_hash)?)
+    }
+}
+
+impl SourceScan {
+    /// Stores `contract` as an account entry plus the shared source record of its code.
+    pub(crate) fn store_contract(&mut self, account_id: &AccountId, contract: &ContractData) {
+        let (entry, record) = contract.clone().split();
+        self.sources.insert(&entry.code_hash, &record);
+        self.contracts
+            .insert(account_id, &VersionedContractData::V2(entry));
+    }
+
+    pub(crate) fn resolve_contract(&self, contract: VersionedContractData) -> ContractData {
+        match contract {
+            VersionedContractData::V1(contract) => contract.into(),
+            VersionedContractData::V2(entry) => {
+                let record = self.sources.get(&entry.code_hash).unwrap_or_else(|| {
+                    env::panic_str(&format!("Missing source record for {}", entry.code_hash))
+                });
+                ContractData::join(entry, record)
+            }
+            VersionedContractData::V3 { entry, source } => {
+                let snapshot = self.snapshot_sources.get(&source).unwrap_or_else(|| {
+                    env::panic_str(&format!("Missing snapshot source for {}", entry.code_hash))
+                });
+                ContractData::join(entry, snapshot.record)
+            }
+        }
+    }
+
+    /// Splits `contract` into the history form, storing its source record once per
+    /// distinct contents.
+    pub(crate) fn snapshot_contract(&mut self, contract: ContractData) -> VersionedContractData {
+        let (entry, record) = contract.split();
+        let source =
+            env::sha256_array(&borsh::to_vec(&record).expect("Failed to serialize source record"));
+        let mut snapshot = self
+            .snapshot_sources
+            .get(&source)
+            .unwrap_or(SnapshotSource { record, refs: 0 });
+        snapshot.refs += 1;
+        self.snapshot_sources.insert(&source, &snapshot);
+
+        VersionedContractData::V3 { entry, source }
+    }
+
+    /// Drops the reference a history snapshot holds on its source record.
+    pub(crate) fn release_snapshot(&mut self, contract: &VersionedContractData) {
+        let VersionedContractData::V3 { source, .. } = contract else {
+            return;
+        };
+        let Some(mut snapshot) = self.snapshot_sources.get(source) else {
+            return;
+        };
+        snapshot.refs -= 1;
+        if snapshot.refs == 0 {
+            self.snapshot_sources.remove(source);
+        } else {
+            self.snapshot_sources.insert(source, &snapshot);
+        }
+    }
+
+    /// Accounts sharing a code hash share its source record, so an entry can only
+    /// change it while no other account references the record.
+    pub(crate) fn check_consistent_source(
+        &self,
+        account_id: &AccountId,
+        contract: &ContractData,
+    ) -> Result<(), SourceScanError> {
+        let Some(record) = self.sources.get(&contract.code_hash) else {
+            return Ok(());
+        };
+        let shared = self
+            .code_hash_index
+            .get(&contract.code_hash)
+            .is_some_and(|accounts| accounts.iter().any(|other| &other != account_id));
+
+        ensure!(
+            !shared || record == contract.clone().split().1,
+            SourceScanError::InvalidInput(format!(
+                "Code hash {} is shared with other accounts; its source record cannot change",
+                contract.code_hash
+            ))
+        );
+        Ok(())
+    }
+
+    /// Drops the source record of `code_hash` once no entry references it any more.
+    /// Relies on the code hash index, so call it after unindexing.
+    pub(crate) fn release_source(&mut self, code_hash: &String) {
+        if self.code_hash_index.get(code_hash).is_none() {
+            self.sources.remove(code_hash);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, code_hash, entry, get_context};
+    use crate::Config;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn accounts_share_source_record() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "factory")
+            .cid(cid("cid1"))
+            .set(&mut contract);
+        entry(accounts(2), "factory")
+            .cid(cid("cid1"))
+            .set(&mut contract);
+
+        let record = contract.get_source_record(code_hash("factory")).unwrap();
+        assert_eq!(record.cid, cid("cid1"));
+
+        // Re-verifying one child must not change the sources shown for the others.
+        assert!(entry(accounts(2), "factory")
+            .cid(cid("cid2"))
+            .try_set(&mut contract)
+            .is_err());
+        assert_eq!(contract.get_contract(accounts(1)).unwrap().cid, cid("cid1"));
+    }
+
+    #[test]
+    fn unreferenced_source_record_is_dropped() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "factory").set(&mut contract);
+        entry(accounts(2), "factory").set(&mut contract);
+
+        contract.purge_contract(accounts(1), None).unwrap();
+        assert!(contract.get_source_record(code_hash("factory")).is_some());
+
+        entry(accounts(2), "upgraded").set(&mut contract);
+        assert!(contract.get_source_record(code_hash("factory")).is_none());
+        assert!(contract.get_source_record(code_hash("upgraded")).is_some());
+
+        contract.purge_contract(accounts(2), None).unwrap();
+        assert!(contract.get_source_record(code_hash("upgraded")).is_none());
+    }
+
+    #[test]
+    fn factory_children_reuse_source_record() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "factory").set(&mut contract);
+
+        contract
+            .set_contracts_for_factory(code_hash("factory"), vec![accounts(2), accounts(3)])
+            .unwrap();
+
+        let child = contract.get_contract(accounts(3)).unwrap();
+        assert_eq!(child.cid, cid("cid"));
+        assert_eq!(child.status, VerificationStatus::Verified);
+        assert_eq!(
+            contract
+                .get_contracts_by_code_hash(code_hash("factory"), 0, 10)
+                .2,
+            3
+        );
+    }
+
+    #[test]
+    fn factory_needs_verified_code_hash() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            contract.set_contracts_for_factory(code_hash("factory"), vec![accounts(2)]),
+            Err(SourceScanError::NotFound(
+                "No source record for code hash AWvXvFL8ZVm5jzrTzV8nRS97yezZEn17fVsX1KJssxxq"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn shared_record_keeps_indexed_fields() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "factory").set(&mut contract);
+        entry(accounts(2), "factory").set(&mut contract);
+
+        testing_env!(get_context(accounts(0)).build());
+        assert_eq!(
+            entry(accounts(2), "factory")
+                .lang(Lang::Go)
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Code hash AWvXvFL8ZVm5jzrTzV8nRS97yezZEn17fVsX1KJssxxq is shared with other \
+                 accounts; its source record cannot change"
+                    .to_string()
+            ))
+        );
+        // A rejected verification must not be announced to indexers.
+        assert!(near_sdk::test_utils::get_logs().is_empty());
+    }
+
+    #[test]
+    fn history_keeps_replaced_sources() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "code")
+            .cid(cid("cid1"))
+            .set(&mut contract);
+        entry(accounts(1), "code")
+            .cid(cid("cid2"))
+            .set(&mut contract);
+        entry(accounts(2), "code")
+            .cid(cid("cid2"))
+            .set(&mut contract);
+
+        let (history, _, _) = contract.get_contract_history(accounts(1), 0, 10);
+        assert_eq!(history[0].contract.cid, cid("cid1"));
+        assert_eq!(history[1].contract.cid, cid("cid2"));
+
+        contract
+            .purge_contracts(vec![accounts(1), accounts(2)], None)
+            .unwrap();
+        for cid_seed in ["cid1", "cid2"] {
+            let record = SourceRecord {
+                cid: cid(cid_seed),
+                lang: Lang::Rust,
+                build_targets: vec!["entry_point".to_string().into()],
+                builder_image: "builder_image".to_string(),
+                source: None,
+                build_info: None,
+            };
+            let source = env::sha256_array(&borsh::to_vec(&record).unwrap());
+            assert!(contract.snapshot_sources.get(&source).is_none());
+        }
+    }
+
+    #[test]
+    fn factory_call_is_logged_once() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "factory").set(&mut contract);
+        let log_len = contract.get_admin_log(0, 100).len();
+
+        testing_env!(get_context(accounts(0)).build());
+        contract
+            .set_contracts_for_factory(code_hash("factory"), vec![accounts(2), accounts(3)])
+            .unwrap();
+
+        let logs = near_sdk::test_utils::get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("contract_set"));
+        let admin_log = contract.get_admin_log(0, 100);
+        assert_eq!(admin_log.len(), log_len + 1);
+        assert_eq!(
+            admin_log.last().unwrap().action,
+            AdminLogAction::SetContractsForFactory {
+                code_hash: code_hash("factory"),
+                account_ids: vec![accounts(2), accounts(3)],
+            }
+        );
+        let (history, _, _) = contract.get_contract_history(accounts(2), 0, 10);
+        assert!(history.is_empty());
+        assert_eq!(contract.get_stats().per_lang, vec![(Lang::Rust, 3)]);
+
+        assert_eq!(
+            contract
+                .set_contracts_for_factory(code_hash("factory"), vec![accounts(4), accounts(4)]),
+            Err(SourceScanError::InvalidInput(
+                "Account ids must be unique".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn factory_batch_is_bounded() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "factory").set(&mut contract);
+        contract
+            .set_config(Config {
+                max_page_size: 2,
+                ..Config::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            contract.set_contracts_for_factory(code_hash("factory"), vec![]),
+            Err(SourceScanError::InvalidInput(
+                "At least one account id is required".to_string()
+            ))
+        );
+        assert_eq!(
+            contract.set_contracts_for_factory(
+                code_hash("factory"),
+                vec![accounts(2), accounts(3), accounts(4)]
+            ),
+            Err(SourceScanError::ContentTooLong(
+                "At most 2 accounts can be set per call".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::cid::normalize_cid;
+use crate::error::{ensure, SourceScanError};
+use crate::events::{AuditAddedData, Event};
+use crate::{AdminLogAction, SourceScan};
+
+const MAX_AUDITOR_LEN: usize = 128;
+
+/// A third-party security audit pinned to a verified entry.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct AuditReport {
+    /// Name of the auditing firm or person.
+    pub auditor: String,
+    /// IPFS CID of the report, in canonical form.
+    pub report_cid: String,
+    /// Publication date of the report as `YYYY-MM-DD`.
+    pub date: String,
+    pub added_by: AccountId,
+    pub added_at: U64,
+}
+
+fn is_iso_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+#[near_bindgen]
+impl SourceScan {
+    #[handle_result]
+    pub fn add_audit(
+        &mut self,
+        account_id: AccountId,
+        auditor: String,
+        report_cid: String,
+        date: String,
+    ) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        ensure!(
+            self.contracts.get(&account_id).is_some(),
+            SourceScanError::no_contract(&account_id)
+        );
+        ensure!(
+            !auditor.trim().is_empty(),
+            SourceScanError::InvalidInput("Auditor is required".to_string())
+        );
+        ensure!(
+            auditor.len() <= MAX_AUDITOR_LEN,
+            SourceScanError::ContentTooLong(format!(
+                "Auditor must be at most {} bytes",
+                MAX_AUDITOR_LEN
+            ))
+        );
+        ensure!(
+            is_iso_date(&date),
+            SourceScanError::InvalidInput(format!("Invalid date {:?}: expected YYYY-MM-DD", date))
+        );
+        let report_cid =
+            normalize_cid(&report_cid).ok_or_else(|| SourceScanError::invalid_cid(&report_cid))?;
+
+        let mut audits = self.audits.get(&account_id).unwrap_or_default();
+        ensure!(
+            audits.iter().all(|audit| audit.report_cid != report_cid),
+            SourceScanError::AlreadyExists("Audit report is already attached".to_string())
+        );
+        audits.push(AuditReport {
+            auditor,
+            report_cid: report_cid.clone(),
+            date,
+            added_by: env::predecessor_account_id(),
+            added_at: U64(env::block_timestamp()),
+        });
+        self.audits.insert(&account_id, &audits);
+        self.log_admin(AdminLogAction::AddAudit {
+            account_id: account_id.clone(),
+            report_cid: report_cid.clone(),
+        });
+
+        Event::AuditAdded(vec![AuditAddedData {
+            account_id,
+            report_cid,
+        }])
+        .emit();
+        Ok(())
+    }
+
+    pub fn get_audits(&self, account_id: AccountId) -> Vec<AuditReport> {
+        self.audits.get(&account_id).unwrap_or_default()
+    }
+}
+
+impl SourceScan {
+    /// Drops the audits of a purged entry.
+    pub(crate) fn clear_audits(&mut self, account_id: &AccountId) {
+        self.audits.remove(account_id);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn with_entry() -> SourceScan {
+        testing_env!(get_context(accounts(0)).block_timestamp(100).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        contract
+    }
+
+    #[test]
+    fn audits_are_attached_and_purged() {
+        let mut contract = with_entry();
+        contract
+            .add_audit(
+                accounts(1),
+                "Example Security".to_string(),
+                cid("report"),
+                "2024-03-01".to_string(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            contract.get_audits(accounts(1)),
+            vec![AuditReport {
+                auditor: "Example Security".to_string(),
+                report_cid: cid("report"),
+                date: "2024-03-01".to_string(),
+                added_by: accounts(0),
+                added_at: U64(100),
+            }]
+        );
+
+        contract.purge_contract(accounts(1), None).unwrap();
+        assert!(contract.get_audits(accounts(1)).is_empty());
+    }
+
+    #[test]
+    fn audit_date_is_validated() {
+        let mut contract = with_entry();
+        assert_eq!(
+            contract.add_audit(
+                accounts(1),
+                "Example Security".to_string(),
+                cid("report"),
+                "01.03.2024".to_string(),
+            ),
+            Err(SourceScanError::InvalidInput(
+                "Invalid date \"01.03.2024\": expected YYYY-MM-DD".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn others_cannot_add_audits() {
+        let mut contract = with_entry();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.add_audit(
+                accounts(1),
+                "Example Security".to_string(),
+                cid("report"),
+                "2024-03-01".to_string(),
+            ),
+            Err(SourceScanError::Unauthorized(
+                "Only owner or verifier can call this method".to_string()
+            ))
+        );
+    }
+}
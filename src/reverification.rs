@@ -0,0 +1,118 @@
+use near_sdk::json_types::U64;
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, ReverificationData};
+use crate::SourceScan;
+
+#[near_bindgen]
+impl SourceScan {
+    /// Queues a stored entry to be checked against its deployed code again, e.g. after a
+    /// redeploy. Callable by anyone; an attached deposit is added to the entry's bounty
+    /// and paid to the verifier that re-verifies it.
+    #[payable]
+    #[handle_result]
+    pub fn request_reverification(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        ensure!(
+            self.contracts.get(&account_id).is_some(),
+            SourceScanError::no_contract(&account_id)
+        );
+
+        if self.pending_reverifications.get(&account_id).is_none() {
+            self.pending_reverifications
+                .insert(&account_id, &U64(env::block_timestamp()));
+        }
+        let deposit = env::attached_deposit();
+        if !deposit.is_zero() {
+            self.add_bounty(account_id.clone(), deposit);
+        }
+
+        Event::ReverificationRequested(vec![ReverificationData {
+            account_id,
+            requested_by: env::predecessor_account_id(),
+        }])
+        .emit();
+        Ok(())
+    }
+
+    /// Queued re-verifications with the time of their first request, for the verifier
+    /// backend to work through.
+    pub fn get_pending_reverifications(
+        &self,
+        from_index: usize,
+        limit: usize,
+    ) -> Vec<(AccountId, U64)> {
+        self.pending_reverifications
+            .iter()
+            .skip(from_index)
+            .take(self.page_limit(limit))
+            .collect()
+    }
+}
+
+impl SourceScan {
+    /// Takes `account_id` off the queue once it is re-verified or purged.
+    pub(crate) fn clear_reverification(&mut self, account_id: &AccountId) {
+        self.pending_reverifications.remove(account_id);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn set(contract: &mut SourceScan) {
+        testing_env!(get_context(accounts(0)).build());
+        set_entry(contract, accounts(1), "code_hash");
+    }
+
+    #[test]
+    fn reverification_is_queued_until_set() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set(&mut contract);
+
+        testing_env!(get_context(accounts(2)).block_timestamp(100).build());
+        contract.request_reverification(accounts(1)).unwrap();
+        testing_env!(get_context(accounts(3))
+            .block_timestamp(200)
+            .attached_deposit(NearToken::from_millinear(50))
+            .build());
+        contract.request_reverification(accounts(1)).unwrap();
+
+        assert_eq!(
+            contract.get_pending_reverifications(0, 10),
+            vec![(accounts(1), U64(100))]
+        );
+        assert_eq!(
+            contract.get_bounties(0, 10),
+            vec![(accounts(1), NearToken::from_millinear(50))]
+        );
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.add_verifier(accounts(4)).unwrap();
+        testing_env!(get_context(accounts(4)).build());
+        set_entry(&mut contract, accounts(1), "code_hash");
+        assert!(contract.get_pending_reverifications(0, 10).is_empty());
+        assert!(contract.get_bounties(0, 10).is_empty());
+    }
+
+    #[test]
+    fn cannot_request_missing_entry() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            contract.request_reverification(accounts(1)),
+            Err(SourceScanError::NotFound(
+                "No verified contract for bob".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::error::SourceScanError;
+use crate::events::{Event, RiskFlagsData};
+use crate::{AdminLogAction, ChangeKind, SourceScan};
+
+/// Standardized risk summary of a verified account, as assessed by a verifier.
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct RiskFlags {
+    pub has_full_access_keys: bool,
+    pub is_upgradable: bool,
+    pub has_owner_methods: bool,
+    pub uses_unsafe_deps: bool,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    #[handle_result]
+    pub fn set_risk_flags(
+        &mut self,
+        account_id: AccountId,
+        risk_flags: RiskFlags,
+    ) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+
+        let mut contract = self
+            .read_contract(&account_id)
+            .ok_or_else(|| SourceScanError::no_contract(&account_id))?;
+        contract.risk_flags = Some(risk_flags);
+        self.touch_contract(&account_id, &mut contract);
+        self.store_contract(&account_id, &contract);
+        self.record_change(&account_id, ChangeKind::RiskFlagsChanged);
+        self.log_admin(AdminLogAction::SetRiskFlags {
+            account_id: account_id.clone(),
+            risk_flags,
+        });
+
+        Event::RiskFlagsSet(vec![RiskFlagsData {
+            account_id,
+            risk_flags,
+        }])
+        .emit();
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn risk_flags_follow_code() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "v1");
+        assert_eq!(contract.get_contract(accounts(1)).unwrap().risk_flags, None);
+
+        let risk_flags = RiskFlags {
+            has_full_access_keys: true,
+            is_upgradable: true,
+            has_owner_methods: false,
+            uses_unsafe_deps: false,
+        };
+        contract.set_risk_flags(accounts(1), risk_flags).unwrap();
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().risk_flags,
+            Some(risk_flags)
+        );
+
+        // Re-verifying the same code keeps the assessment, new code clears it.
+        set_entry(&mut contract, accounts(1), "v1");
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().risk_flags,
+            Some(risk_flags)
+        );
+        set_entry(&mut contract, accounts(1), "v2");
+        assert_eq!(contract.get_contract(accounts(1)).unwrap().risk_flags, None);
+    }
+}
@@ -0,0 +1,218 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, RoleChangedData};
+use crate::{AdminLogAction, SourceScan};
+
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum Role {
+    Verifier,
+    Moderator,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    #[handle_result]
+    pub fn add_verifier(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        self.insert_verifier(account_id);
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn remove_verifier(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        self.drop_verifier(account_id);
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn add_moderator(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        if self.moderators.insert(&account_id) {
+            self.log_admin(AdminLogAction::GrantRole {
+                account_id: account_id.clone(),
+                role: Role::Moderator,
+            });
+            Event::RoleGranted(vec![RoleChangedData {
+                account_id,
+                role: Role::Moderator,
+            }])
+            .emit();
+        }
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn remove_moderator(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        if self.moderators.remove(&account_id) {
+            self.log_admin(AdminLogAction::RevokeRole {
+                account_id: account_id.clone(),
+                role: Role::Moderator,
+            });
+            Event::RoleRevoked(vec![RoleChangedData {
+                account_id,
+                role: Role::Moderator,
+            }])
+            .emit();
+        }
+        Ok(())
+    }
+
+    pub fn get_verifiers(&self) -> Vec<AccountId> {
+        self.verifiers.to_vec()
+    }
+
+    pub fn get_moderators(&self) -> Vec<AccountId> {
+        self.moderators.to_vec()
+    }
+}
+
+impl SourceScan {
+    pub(crate) fn insert_verifier(&mut self, account_id: AccountId) {
+        if self.verifiers.insert(&account_id) {
+            self.log_admin(AdminLogAction::GrantRole {
+                account_id: account_id.clone(),
+                role: Role::Verifier,
+            });
+            Event::RoleGranted(vec![RoleChangedData {
+                account_id,
+                role: Role::Verifier,
+            }])
+            .emit();
+        }
+    }
+
+    pub(crate) fn drop_verifier(&mut self, account_id: AccountId) {
+        if self.verifiers.remove(&account_id) {
+            self.log_admin(AdminLogAction::RevokeRole {
+                account_id: account_id.clone(),
+                role: Role::Verifier,
+            });
+            Event::RoleRevoked(vec![RoleChangedData {
+                account_id,
+                role: Role::Verifier,
+            }])
+            .emit();
+        }
+    }
+
+    pub(crate) fn check_owner(&self) -> Result<(), SourceScanError> {
+        ensure!(
+            env::predecessor_account_id() == self.owner_id,
+            SourceScanError::Unauthorized("Only owner can call this method".to_string())
+        );
+        Ok(())
+    }
+
+    /// The owner implicitly holds every role.
+    pub(crate) fn has_role(&self, account_id: &AccountId, role: Role) -> bool {
+        if *account_id == self.owner_id {
+            return true;
+        }
+
+        match role {
+            Role::Verifier => self.verifiers.contains(account_id),
+            Role::Moderator => self.moderators.contains(account_id),
+        }
+    }
+
+    pub(crate) fn check_verifier(&self) -> Result<(), SourceScanError> {
+        ensure!(
+            self.has_role(&env::predecessor_account_id(), Role::Verifier),
+            SourceScanError::Unauthorized(
+                "Only owner or verifier can call this method".to_string()
+            )
+        );
+        Ok(())
+    }
+
+    pub(crate) fn check_moderator(&self) -> Result<(), SourceScanError> {
+        ensure!(
+            self.has_role(&env::predecessor_account_id(), Role::Moderator),
+            SourceScanError::Unauthorized(
+                "Only owner or moderator can call this method".to_string()
+            )
+        );
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{entry, get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn verifier_can_set_and_purge_contracts() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+        assert_eq!(contract.get_verifiers(), vec![accounts(1)]);
+
+        testing_env!(get_context(accounts(1)).build());
+        set_entry(&mut contract, accounts(2), "code_hash");
+        assert!(contract.get_contract(accounts(2)).is_some());
+
+        contract.purge_contract(accounts(2), None).unwrap();
+        assert!(contract.get_contract(accounts(2)).is_none());
+    }
+
+    #[test]
+    fn removed_verifier_cannot_set_contract() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+        contract.remove_verifier(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            entry(accounts(2), "code_hash").try_set(&mut contract),
+            Err(SourceScanError::Unauthorized(
+                "Only owner or verifier can call this method".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn verifier_cannot_grant_roles() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_verifier(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.add_moderator(accounts(2)),
+            Err(SourceScanError::Unauthorized(
+                "Only owner can call this method".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn owner_holds_every_role() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_moderator(accounts(1)).unwrap();
+
+        assert!(contract.has_role(&accounts(0), Role::Moderator));
+        assert!(contract.has_role(&accounts(0), Role::Verifier));
+        assert!(contract.has_role(&accounts(1), Role::Moderator));
+        assert!(!contract.has_role(&accounts(1), Role::Verifier));
+    }
+}
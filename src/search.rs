@@ -0,0 +1,590 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+use std::ops::Bound;
+
+use crate::indexes::{component_index_key, parent_accounts};
+use crate::{ContractData, Lang, Network, RepoSource, SourceScan, VerificationStatus};
+
+/// Optional criteria `search` combines with its text query. Unset fields match every
+/// entry.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct SearchFilters {
+    pub lang: Option<Lang>,
+    pub builder_image: Option<String>,
+    /// Only entries whose sources are (`true`) or are not (`false`) hosted on GitHub.
+    pub has_github: Option<bool>,
+    pub status: Option<StatusFilter>,
+    pub tag: Option<String>,
+    pub network: Option<Network>,
+}
+
+/// The first component index key of `account_id` that `prefix` matches; an account is
+/// only listed at that key so it appears once.
+fn first_component_key(account_id: &AccountId, prefix: &str) -> Option<String> {
+    parent_accounts(account_id)
+        .iter()
+        .map(|parent| component_index_key(parent, account_id))
+        .filter(|key| key.starts_with(prefix))
+        .min()
+}
+
+/// Whether the name of `account_id` without its top-level account contains `query`, so
+/// a query like `ear` does not match every `.near` account.
+fn name_contains(account_id: &str, query: &str) -> bool {
+    let name = account_id
+        .rsplit_once('.')
+        .map_or(account_id, |(name, _)| name);
+    name.contains(query)
+}
+
+/// `VerificationStatus` without the details of a revocation, for filtering.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum StatusFilter {
+    Pending,
+    Verified,
+    Stale,
+    Revoked,
+    Expired,
+}
+
+impl StatusFilter {
+    fn matches(self, status: &VerificationStatus) -> bool {
+        matches!(
+            (self, status),
+            (StatusFilter::Pending, VerificationStatus::Pending)
+                | (StatusFilter::Verified, VerificationStatus::Verified)
+                | (StatusFilter::Stale, VerificationStatus::Stale)
+                | (StatusFilter::Revoked, VerificationStatus::Revoked { .. })
+                | (StatusFilter::Expired, VerificationStatus::Expired)
+        )
+    }
+}
+
+/// Where a `search` call stopped scanning; pass it back to continue from there.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SearchCursor {
+    /// Among the exact matches, after this many of them.
+    Exact(u32),
+    /// Among the accounts starting with the query, after this account.
+    Prefix(String),
+    /// Among the sub-account component matches, after this component index key.
+    Component(String),
+    /// Among the accounts containing the query elsewhere, after this account.
+    Substring(String),
+}
+
+/// Results of one `search` call.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SearchPage {
+    pub results: Vec<(AccountId, ContractData)>,
+    /// `None` once every match was scanned.
+    pub next_cursor: Option<SearchCursor>,
+    /// Number of entries matching the query and filters. Only known when one call, made
+    /// without a cursor, scanned every match; a bounded scan cannot count entries it did
+    /// not reach, so this is `None` otherwise.
+    pub total_count: Option<u64>,
+}
+
+type Candidates<'a> = Box<dyn Iterator<Item = (SearchCursor, Option<AccountId>)> + 'a>;
+
+#[near_bindgen]
+impl SourceScan {
+    /// Returns entries with an account component starting with `key`, so `signer` finds
+    /// `v1.signer.near`, that also pass `filters`. Exact matches come first, then accounts
+    /// starting with `key` in account id order, then the rest by matching component, then
+    /// accounts whose name contains `key` anywhere, so `ample` finds `example.near`.
+    ///
+    /// A call examines at most `max_scan` index keys, capped at `Config::max_search_scan`,
+    /// so it may return fewer than `limit` results; continue from `next_cursor` until it
+    /// is `None`.
+    pub fn search(
+        &self,
+        key: String,
+        cursor: Option<SearchCursor>,
+        limit: usize,
+        filters: Option<SearchFilters>,
+        max_scan: Option<u32>,
+    ) -> SearchPage {
+        let limit = self.page_limit(limit);
+        let max_scan = self.scan_limit(max_scan);
+        let filters = filters.unwrap_or_default();
+        let query = key.trim().to_lowercase();
+        let prefix = self.strip_search_suffix(&query);
+        let exact = self.exact_matches(&query, prefix);
+
+        let first_page = cursor.is_none();
+        let mut results = Vec::new();
+        let mut last = None;
+        let candidates = self.search_candidates(prefix, &exact, cursor);
+        for (scanned, (position, account_id)) in candidates.enumerate() {
+            if results.len() == limit || scanned == max_scan as usize {
+                return SearchPage {
+                    results,
+                    next_cursor: last,
+                    total_count: None,
+                };
+            }
+            last = Some(position);
+
+            let Some(account_id) = account_id else {
+                continue;
+            };
+            let Some(contract) = self.view_contract(&account_id) else {
+                continue;
+            };
+            if self.matches_filters(&account_id, &contract, &filters) {
+                results.push((account_id, contract));
+            }
+        }
+
+        let total_count = first_page.then_some(results.len() as u64);
+        SearchPage {
+            results,
+            next_cursor: None,
+            total_count,
+        }
+    }
+}
+
+impl SourceScan {
+    /// Validates the `max_scan` of a `search` call and caps it at `Config::max_search_scan`.
+    fn scan_limit(&self, max_scan: Option<u32>) -> u32 {
+        let max_search_scan = self.config.max_search_scan;
+        let max_scan = max_scan.unwrap_or(max_search_scan);
+        assert!(max_scan > 0, "max_scan must be greater than 0");
+
+        max_scan.min(max_search_scan)
+    }
+
+    /// Strips one configured search suffix, e.g. `.near`, from a lowercased query.
+    fn strip_search_suffix<'a>(&self, query: &'a str) -> &'a str {
+        self.search_suffixes
+            .iter()
+            .find_map(|suffix| query.strip_suffix(&format!(".{}", suffix)))
+            .unwrap_or(query)
+    }
+
+    /// Registered accounts named exactly `query`, or `prefix` with or without a search
+    /// suffix, in account id order.
+    fn exact_matches(&self, query: &str, prefix: &str) -> Vec<AccountId> {
+        let mut names = vec![query.to_string(), prefix.to_string()];
+        names.extend(
+            self.search_suffixes
+                .iter()
+                .map(|suffix| format!("{}.{}", prefix, suffix)),
+        );
+        names.sort();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter(|name| self.search_index.contains_key(name))
+            .filter_map(|name| name.parse().ok())
+            .collect()
+    }
+
+    /// Every index key `search` scans after `cursor`, in result order, with the cursor
+    /// that resumes after it. Keys of accounts listed earlier yield no account.
+    fn search_candidates<'a>(
+        &'a self,
+        prefix: &'a str,
+        exact: &'a [AccountId],
+        cursor: Option<SearchCursor>,
+    ) -> Candidates<'a> {
+        let from_prefix = || Some(Bound::Included(prefix.to_string()));
+        let (skip_exact, prefix_from, component_from, substring_from) = match cursor {
+            None => (0, from_prefix(), from_prefix(), Bound::Unbounded),
+            Some(SearchCursor::Exact(count)) => (
+                count as usize,
+                from_prefix(),
+                from_prefix(),
+                Bound::Unbounded,
+            ),
+            Some(SearchCursor::Prefix(key)) => (
+                exact.len(),
+                Some(Bound::Excluded(key)),
+                from_prefix(),
+                Bound::Unbounded,
+            ),
+            Some(SearchCursor::Component(key)) => (
+                exact.len(),
+                None,
+                Some(Bound::Excluded(key)),
+                Bound::Unbounded,
+            ),
+            Some(SearchCursor::Substring(key)) => (exact.len(), None, None, Bound::Excluded(key)),
+        };
+
+        let exact_matches = exact
+            .iter()
+            .enumerate()
+            .skip(skip_exact)
+            .map(|(index, account_id)| {
+                (
+                    SearchCursor::Exact(index as u32 + 1),
+                    Some(account_id.clone()),
+                )
+            });
+        let prefix_matches = prefix_from.into_iter().flat_map(move |from| {
+            self.search_index
+                .range((from, Bound::Unbounded))
+                .take_while(move |(key, _)| key.starts_with(prefix))
+                .map(move |(key, account_id)| {
+                    let account_id = (!exact.contains(&account_id)).then_some(account_id);
+                    (SearchCursor::Prefix(key), account_id)
+                })
+        });
+        let component_matches = component_from.into_iter().flat_map(move |from| {
+            self.component_index
+                .range((from, Bound::Unbounded))
+                .take_while(move |(key, _)| key.starts_with(prefix))
+                .map(move |(key, account_id)| {
+                    let account_id = (!account_id.as_str().starts_with(prefix)
+                        && first_component_key(&account_id, prefix).as_ref() == Some(&key))
+                    .then_some(account_id);
+                    (SearchCursor::Component(key), account_id)
+                })
+        });
+        // Every account starting with `prefix` also contains it, so an empty query has
+        // nothing left to find here.
+        let substring_matches = (!prefix.is_empty())
+            .then(|| {
+                self.search_index
+                    .range((substring_from, Bound::Unbounded))
+                    .map(move |(key, account_id)| {
+                        let account_id = (name_contains(&key, prefix)
+                            && !key.starts_with(prefix)
+                            && first_component_key(&account_id, prefix).is_none())
+                        .then_some(account_id);
+                        (SearchCursor::Substring(key), account_id)
+                    })
+            })
+            .into_iter()
+            .flatten();
+
+        Box::new(
+            exact_matches
+                .chain(prefix_matches)
+                .chain(component_matches)
+                .chain(substring_matches),
+        )
+    }
+
+    /// Whether the entry of `account_id`, as views report it, passes every set filter.
+    pub(crate) fn matches_filters(
+        &self,
+        account_id: &AccountId,
+        contract: &ContractData,
+        filters: &SearchFilters,
+    ) -> bool {
+        let has_github = matches!(contract.source, Some(RepoSource::Github(_)));
+
+        filters.lang.iter().all(|lang| contract.lang == *lang)
+            && filters
+                .builder_image
+                .iter()
+                .all(|image| contract.builder_image == *image)
+            && filters
+                .has_github
+                .iter()
+                .all(|&wanted| has_github == wanted)
+            && filters
+                .status
+                .iter()
+                .all(|status| status.matches(&contract.status))
+            && filters
+                .network
+                .iter()
+                .all(|network| contract.network == *network)
+            && filters.tag.iter().all(|tag| {
+                self.contract_tags
+                    .get(account_id)
+                    .is_some_and(|tags| tags.contains(&tag.trim().to_lowercase()))
+            })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{entry, get_context, set_entry};
+    use crate::{Config, RepoData};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn github() -> RepoSource {
+        RepoSource::Github(RepoData {
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            path: None,
+            git_ref: None,
+        })
+    }
+
+    fn ids(contract: &SourceScan, filters: SearchFilters) -> Vec<String> {
+        contract
+            .search("app".to_string(), None, 10, Some(filters), None)
+            .results
+            .into_iter()
+            .map(|(account_id, _)| account_id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn search_combines_query_and_filters() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry("app1.near".parse().unwrap(), "app1.near")
+            .source(github())
+            .set(&mut contract);
+        entry("app2.near".parse().unwrap(), "app2.near").set(&mut contract);
+        entry("app3.near".parse().unwrap(), "app3.near")
+            .lang(Lang::JavaScript)
+            .source(github())
+            .set(&mut contract);
+        entry("other.near".parse().unwrap(), "other.near")
+            .source(github())
+            .set(&mut contract);
+        contract
+            .add_tag("app2.near".parse().unwrap(), "DeFi".to_string())
+            .unwrap();
+        contract
+            .revoke_contract("app3.near".parse().unwrap(), "wrong sources".to_string())
+            .unwrap();
+
+        assert_eq!(
+            ids(
+                &contract,
+                SearchFilters {
+                    lang: Some(Lang::Rust),
+                    ..SearchFilters::default()
+                }
+            ),
+            vec!["app1.near", "app2.near"]
+        );
+        assert_eq!(
+            ids(
+                &contract,
+                SearchFilters {
+                    lang: Some(Lang::Rust),
+                    has_github: Some(true),
+                    ..SearchFilters::default()
+                }
+            ),
+            vec!["app1.near"]
+        );
+        assert_eq!(
+            ids(
+                &contract,
+                SearchFilters {
+                    tag: Some("defi".to_string()),
+                    builder_image: Some("builder_image".to_string()),
+                    ..SearchFilters::default()
+                }
+            ),
+            vec!["app2.near"]
+        );
+
+        assert_eq!(
+            ids(
+                &contract,
+                SearchFilters {
+                    status: Some(StatusFilter::Revoked),
+                    ..SearchFilters::default()
+                }
+            ),
+            vec!["app3.near"]
+        );
+    }
+
+    /// Every result for `key`, one per call so each test also walks the cursors.
+    fn find(contract: &SourceScan, key: &str) -> Vec<String> {
+        let mut found = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = contract.search(key.to_string(), cursor, 1, None, None);
+            found.extend(
+                page.results
+                    .into_iter()
+                    .map(|(account_id, _)| account_id.to_string()),
+            );
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return found;
+            }
+        }
+    }
+
+    #[test]
+    fn search_matches_normalized_prefixes() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        for account_id in ["foo.near", "foo.testnet", "foobar.near", "barfoo.near"] {
+            set_entry(&mut contract, account_id.parse().unwrap(), account_id);
+        }
+
+        assert_eq!(
+            find(&contract, "foo"),
+            vec!["foo.near", "foo.testnet", "foobar.near", "barfoo.near"]
+        );
+        assert_eq!(find(&contract, "FOO.near").len(), 4);
+
+        contract
+            .purge_contract("foobar.near".parse().unwrap(), None)
+            .unwrap();
+        assert!(find(&contract, "foob").is_empty());
+    }
+
+    #[test]
+    fn search_matches_sub_account_components() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        for account_id in ["v1.signer.near", "signer.testnet", "a.ab.near", "near"] {
+            set_entry(&mut contract, account_id.parse().unwrap(), account_id);
+        }
+
+        assert_eq!(
+            find(&contract, "signer"),
+            vec!["signer.testnet", "v1.signer.near"]
+        );
+        assert_eq!(find(&contract, "v1.signer.near"), vec!["v1.signer.near"]);
+        // Matching both components still lists the account once.
+        assert_eq!(find(&contract, "a"), vec!["a.ab.near", "near"]);
+        // Top-level components are only indexed for top-level accounts.
+        assert_eq!(find(&contract, "nea"), vec!["near"]);
+
+        assert_eq!(find(&contract, "signer.testnet").len(), 2);
+        contract
+            .set_search_suffixes(vec!["near".parse().unwrap()])
+            .unwrap();
+        assert_eq!(contract.get_search_suffixes(), vec!["near"]);
+        assert_eq!(find(&contract, "signer.testnet"), vec!["signer.testnet"]);
+
+        contract
+            .purge_contract("v1.signer.near".parse().unwrap(), None)
+            .unwrap();
+        assert_eq!(find(&contract, "signer"), vec!["signer.testnet"]);
+    }
+
+    #[test]
+    fn search_ranks_exact_then_prefix_then_component_matches() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        for account_id in [
+            "a.ref.near",
+            "ref-finance.near",
+            "v2.ref-finance.near",
+            "ref.near",
+            "refuel.testnet",
+        ] {
+            set_entry(&mut contract, account_id.parse().unwrap(), account_id);
+        }
+
+        assert_eq!(
+            find(&contract, "ref"),
+            vec![
+                "ref.near",
+                "ref-finance.near",
+                "refuel.testnet",
+                "v2.ref-finance.near",
+                "a.ref.near",
+            ]
+        );
+    }
+
+    #[test]
+    fn search_scan_is_bounded() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        for account_id in [
+            "ref.near",
+            "ref-finance.near",
+            "refuel.near",
+            "a.ref.near",
+            "preferred.near",
+        ] {
+            set_entry(&mut contract, account_id.parse().unwrap(), account_id);
+        }
+
+        let page = contract.search("ref".to_string(), None, 10, None, None);
+        assert_eq!(page.total_count, Some(5));
+
+        let page = contract.search("ref".to_string(), None, 10, None, Some(2));
+        assert_eq!(page.results.len(), 2);
+        assert_eq!(page.total_count, None);
+        assert_eq!(
+            page.next_cursor,
+            Some(SearchCursor::Prefix("ref-finance.near".to_string()))
+        );
+
+        // Skipping the exact match already returned still counts towards the scan.
+        let page = contract.search("ref".to_string(), page.next_cursor, 10, None, Some(2));
+        assert_eq!(page.results[0].0, "refuel.near");
+        assert_eq!(
+            page.next_cursor,
+            Some(SearchCursor::Prefix("refuel.near".to_string()))
+        );
+
+        let page = contract.search("ref".to_string(), page.next_cursor, 10, None, Some(2));
+        assert_eq!(page.results[0].0, "a.ref.near");
+        assert_eq!(
+            page.next_cursor,
+            Some(SearchCursor::Substring("a.ref.near".to_string()))
+        );
+
+        // The substring tier walks every account, so it is bounded the same way.
+        let page = contract.search("ref".to_string(), page.next_cursor, 10, None, Some(2));
+        assert_eq!(page.results[0].0, "preferred.near");
+        assert_eq!(
+            page.next_cursor,
+            Some(SearchCursor::Substring("ref-finance.near".to_string()))
+        );
+        let page = contract.search("ref".to_string(), page.next_cursor, 10, None, Some(10));
+        assert!(page.results.is_empty());
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(page.total_count, None);
+
+        contract
+            .set_config(Config {
+                max_search_scan: 1,
+                ..Config::default()
+            })
+            .unwrap();
+        let page = contract.search("ref".to_string(), None, 10, None, Some(100));
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.next_cursor, Some(SearchCursor::Exact(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_scan must be greater than 0")]
+    fn search_rejects_zero_max_scan() {
+        testing_env!(get_context(accounts(0)).build());
+        let contract = SourceScan::new();
+
+        contract.search("ref".to_string(), None, 10, None, Some(0));
+    }
+
+    #[test]
+    fn filters_default_when_omitted() {
+        assert_eq!(
+            near_sdk::serde_json::from_str::<SearchFilters>(r#"{"status":"revoked"}"#).unwrap(),
+            SearchFilters {
+                status: Some(StatusFilter::Revoked),
+                ..SearchFilters::default()
+            }
+        );
+    }
+}
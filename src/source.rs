@@ -0,0 +1,141 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+
+/// A repository on one of the hosts `RepoSource` knows the URL layout of.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct RepoData {
+    pub owner: String,
+    pub repo: String,
+    /// Full 40-character commit hash the contract was built from.
+    pub sha: String,
+    /// Directory of the contract crate inside the repository, e.g. for workspaces.
+    pub path: Option<String>,
+    /// Branch or tag the commit was taken from.
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+/// A repository on any other host, addressed by its clone URL.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct GitData {
+    /// `https://` URL of the repository.
+    pub url: String,
+    pub sha: String,
+    /// Directory of the contract crate inside the repository, e.g. for workspaces.
+    pub path: Option<String>,
+    /// Branch or tag the commit was taken from.
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+/// Where the verified sources are hosted.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum RepoSource {
+    Github(RepoData),
+    Gitlab(RepoData),
+    Codeberg(RepoData),
+    Git(GitData),
+}
+
+impl RepoSource {
+    pub fn sha(&self) -> &str {
+        match self {
+            RepoSource::Github(repo) | RepoSource::Gitlab(repo) | RepoSource::Codeberg(repo) => {
+                &repo.sha
+            }
+            RepoSource::Git(git) => &git.sha,
+        }
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            RepoSource::Github(repo) | RepoSource::Gitlab(repo) | RepoSource::Codeberg(repo) => {
+                repo.path.as_deref()
+            }
+            RepoSource::Git(git) => git.path.as_deref(),
+        }
+    }
+
+    pub fn git_ref(&self) -> Option<&str> {
+        match self {
+            RepoSource::Github(repo) | RepoSource::Gitlab(repo) | RepoSource::Codeberg(repo) => {
+                repo.git_ref.as_deref()
+            }
+            RepoSource::Git(git) => git.git_ref.as_deref(),
+        }
+    }
+
+    /// Browsable link to the sources at the verified commit, down to `path` if set.
+    /// Generic git sources only have their clone URL.
+    pub fn link(&self) -> String {
+        let path = self
+            .path()
+            .map_or(String::new(), |path| format!("/{}", path));
+        match self {
+            RepoSource::Github(repo) => format!(
+                "https://github.com/{}/{}/tree/{}{}",
+                repo.owner, repo.repo, repo.sha, path
+            ),
+            RepoSource::Gitlab(repo) => format!(
+                "https://gitlab.com/{}/{}/-/tree/{}{}",
+                repo.owner, repo.repo, repo.sha, path
+            ),
+            RepoSource::Codeberg(repo) => format!(
+                "https://codeberg.org/{}/{}/src/commit/{}{}",
+                repo.owner, repo.repo, repo.sha, path
+            ),
+            RepoSource::Git(git) => git.url.clone(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_follow_host_layout() {
+        let repo = RepoData {
+            owner: "near".to_string(),
+            repo: "core-contracts".to_string(),
+            sha: "abc".to_string(),
+            path: None,
+            git_ref: None,
+        };
+
+        assert_eq!(
+            RepoSource::Gitlab(repo.clone()).link(),
+            "https://gitlab.com/near/core-contracts/-/tree/abc"
+        );
+        assert_eq!(
+            RepoSource::Codeberg(repo.clone()).link(),
+            "https://codeberg.org/near/core-contracts/src/commit/abc"
+        );
+        assert_eq!(
+            RepoSource::Github(RepoData {
+                path: Some("contracts/ft".to_string()),
+                ..repo
+            })
+            .link(),
+            "https://github.com/near/core-contracts/tree/abc/contracts/ft"
+        );
+        assert_eq!(
+            RepoSource::Git(GitData {
+                url: "https://git.example.org/contracts.git".to_string(),
+                sha: "abc".to_string(),
+                path: None,
+                git_ref: None,
+            })
+            .link(),
+            "https://git.example.org/contracts.git"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId, NearToken, Promise};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, StakeData};
+use crate::{AdminLogAction, SourceScan};
+
+/// Time a stake stays slashable after its verifier unregisters.
+const UNBONDING_PERIOD: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct VerifierStake {
+    pub amount: NearToken,
+    /// Set by `unregister_verifier`; the stake can be withdrawn from this timestamp.
+    pub withdrawable_at: Option<U64>,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Stakes the attached deposit and grants the verifier role once the stake reaches
+    /// `Config::min_verifier_stake`. Registering again tops up the stake and cancels a
+    /// pending withdrawal.
+    #[payable]
+    #[handle_result]
+    pub fn register_verifier(&mut self) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        let min_stake = self.config.min_verifier_stake;
+        ensure!(
+            !min_stake.is_zero(),
+            SourceScanError::InvalidState("Verifier staking is disabled".to_string())
+        );
+        let account_id = env::predecessor_account_id();
+        let deposit = env::attached_deposit();
+
+        let amount = self
+            .verifier_stakes
+            .get(&account_id)
+            .map_or(NearToken::from_yoctonear(0), |stake| stake.amount)
+            .saturating_add(deposit);
+        ensure!(
+            amount >= min_stake,
+            SourceScanError::InsufficientDeposit(format!(
+                "Stake at least {} to register as a verifier",
+                min_stake
+            ))
+        );
+
+        self.verifier_stakes.insert(
+            &account_id,
+            &VerifierStake {
+                amount,
+                withdrawable_at: None,
+            },
+        );
+        self.insert_verifier(account_id.clone());
+
+        Event::VerifierStaked(vec![StakeData { account_id, amount }]).emit();
+        Ok(())
+    }
+
+    /// Gives up the verifier role and starts the unbonding period of the stake.
+    #[handle_result]
+    pub fn unregister_verifier(&mut self) -> Result<(), SourceScanError> {
+        let account_id = env::predecessor_account_id();
+        let mut stake = self.stake_of(&account_id)?;
+        ensure!(
+            stake.withdrawable_at.is_none(),
+            SourceScanError::InvalidState("Stake is already unbonding".to_string())
+        );
+
+        stake.withdrawable_at = Some(U64(env::block_timestamp() + UNBONDING_PERIOD));
+        self.verifier_stakes.insert(&account_id, &stake);
+        self.drop_verifier(account_id);
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn withdraw_stake(&mut self) -> Result<Promise, SourceScanError> {
+        let account_id = env::predecessor_account_id();
+        let stake = self.stake_of(&account_id)?;
+        ensure!(
+            stake
+                .withdrawable_at
+                .is_some_and(|at| env::block_timestamp() >= at.0),
+            SourceScanError::InvalidState("Stake is still bonded".to_string())
+        );
+
+        self.verifier_stakes.remove(&account_id);
+        Ok(Promise::new(account_id).transfer(stake.amount))
+    }
+
+    /// Takes up to `amount` from the stake of `account_id` for a bad verification. The
+    /// slashed funds stay with the registry. Drops the verifier role if the remaining
+    /// stake falls below the minimum.
+    #[handle_result]
+    pub fn slash_verifier(
+        &mut self,
+        account_id: AccountId,
+        amount: NearToken,
+    ) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        let mut stake = self.stake_of(&account_id)?;
+
+        let slashed = if amount < stake.amount {
+            amount
+        } else {
+            stake.amount
+        };
+        stake.amount = stake.amount.saturating_sub(slashed);
+        if stake.amount.is_zero() {
+            self.verifier_stakes.remove(&account_id);
+        } else {
+            self.verifier_stakes.insert(&account_id, &stake);
+        }
+        if stake.amount < self.config.min_verifier_stake {
+            self.drop_verifier(account_id.clone());
+        }
+        self.log_admin(AdminLogAction::SlashVerifier {
+            account_id: account_id.clone(),
+            amount: slashed,
+        });
+
+        Event::VerifierSlashed(vec![StakeData {
+            account_id,
+            amount: slashed,
+        }])
+        .emit();
+        Ok(())
+    }
+
+    pub fn get_verifier_stake(&self, account_id: AccountId) -> Option<VerifierStake> {
+        self.verifier_stakes.get(&account_id)
+    }
+}
+
+impl SourceScan {
+    fn stake_of(&self, account_id: &AccountId) -> Result<VerifierStake, SourceScanError> {
+        self.verifier_stakes.get(account_id).ok_or_else(|| {
+            SourceScanError::NotFound(format!("{} has no verifier stake", account_id))
+        })
+    }
+
+    /// Once a minimum stake is configured, only the owner and verifiers holding a bonded
+    /// stake of at least that amount can store verifications.
+    pub(crate) fn check_staked(&self) -> Result<(), SourceScanError> {
+        let min_stake = self.config.min_verifier_stake;
+        let predecessor = env::predecessor_account_id();
+        if min_stake.is_zero() || predecessor == self.owner_id {
+            return Ok(());
+        }
+
+        ensure!(
+            self.verifier_stakes
+                .get(&predecessor)
+                .is_some_and(|stake| stake.withdrawable_at.is_none() && stake.amount >= min_stake),
+            SourceScanError::Unauthorized(
+                "Only staked verifiers can store verifications".to_string()
+            )
+        );
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{entry, get_context};
+    use crate::Config;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const MIN_STAKE: NearToken = NearToken::from_near(10);
+
+    fn staking_enabled() -> SourceScan {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_config(Config {
+                min_verifier_stake: MIN_STAKE,
+                ..Config::default()
+            })
+            .unwrap();
+        contract
+    }
+
+    fn register(contract: &mut SourceScan, account_id: AccountId, deposit: NearToken) {
+        testing_env!(get_context(account_id).attached_deposit(deposit).build());
+        contract.register_verifier().unwrap();
+    }
+
+    #[test]
+    fn staked_verifier_lifecycle() {
+        let mut contract = staking_enabled();
+        register(&mut contract, accounts(1), MIN_STAKE);
+        assert_eq!(contract.get_verifiers(), vec![accounts(1)]);
+
+        testing_env!(get_context(accounts(1)).block_timestamp(100).build());
+        entry(accounts(3), "code_hash").set(&mut contract);
+        contract.unregister_verifier().unwrap();
+        assert!(contract.get_verifiers().is_empty());
+        assert_eq!(
+            contract
+                .get_verifier_stake(accounts(1))
+                .unwrap()
+                .withdrawable_at,
+            Some(U64(100 + UNBONDING_PERIOD))
+        );
+
+        testing_env!(get_context(accounts(1))
+            .block_timestamp(100 + UNBONDING_PERIOD)
+            .build());
+        contract.withdraw_stake().unwrap();
+        assert!(contract.get_verifier_stake(accounts(1)).is_none());
+    }
+
+    #[test]
+    fn slashing_below_minimum_drops_role() {
+        let mut contract = staking_enabled();
+        register(&mut contract, accounts(1), MIN_STAKE);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract
+            .slash_verifier(accounts(1), NearToken::from_near(4))
+            .unwrap();
+
+        assert_eq!(
+            contract.get_verifier_stake(accounts(1)).unwrap().amount,
+            NearToken::from_near(6)
+        );
+        assert!(contract.get_verifiers().is_empty());
+    }
+
+    #[test]
+    fn unstaked_verifier_cannot_set_contract() {
+        let mut contract = staking_enabled();
+        contract.add_verifier(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            entry(accounts(3), "code_hash").try_set(&mut contract),
+            Err(SourceScanError::Unauthorized(
+                "Only staked verifiers can store verifications".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn stake_cannot_be_withdrawn_early() {
+        let mut contract = staking_enabled();
+        register(&mut contract, accounts(1), MIN_STAKE);
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.unregister_verifier().unwrap();
+        assert_eq!(
+            contract.withdraw_stake().err(),
+            Some(SourceScanError::InvalidState(
+                "Stake is still bonded".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedMap;
+use near_sdk::near_bindgen;
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::{ContractData, Lang, SourceScan};
+
+/// Registry totals, maintained as counters when entries are written or purged.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Stats {
+    pub total_contracts: u64,
+    pub per_lang: Vec<(Lang, u64)>,
+    pub per_builder_image: Vec<(String, u64)>,
+}
+
+fn adjust_count<K: BorshSerialize + BorshDeserialize>(
+    counts: &mut UnorderedMap<K, u64>,
+    key: &K,
+    added: bool,
+    by: u64,
+) {
+    let count = counts.get(key).unwrap_or(0);
+    let count = if added {
+        count + by
+    } else {
+        count.saturating_sub(by)
+    };
+
+    if count == 0 {
+        counts.remove(key);
+    } else {
+        counts.insert(key, &count);
+    }
+}
+
+#[near_bindgen]
+impl SourceScan {
+    pub fn get_stats(&self) -> Stats {
+        Stats {
+            total_contracts: self.contracts.len(),
+            per_lang: self.lang_counts.to_vec(),
+            per_builder_image: self.builder_image_counts.to_vec(),
+        }
+    }
+}
+
+impl SourceScan {
+    /// Adds `count` entries with the source metadata of `contract` to, or removes them
+    /// from, the per-field counters.
+    pub(crate) fn count_contracts(&mut self, contract: &ContractData, added: bool, count: u64) {
+        adjust_count(&mut self.lang_counts, &contract.lang, added, count);
+        adjust_count(
+            &mut self.builder_image_counts,
+            &contract.builder_image,
+            added,
+            count,
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{entry, get_context};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn stats_follow_writes_and_purges() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        entry(accounts(1), "hash1")
+            .image("image1")
+            .set(&mut contract);
+        entry(accounts(2), "hash2")
+            .image("image2")
+            .set(&mut contract);
+        entry(accounts(3), "hash3")
+            .lang(Lang::Go)
+            .image("image1")
+            .set(&mut contract);
+        entry(accounts(2), "hash2")
+            .lang(Lang::AssemblyScript)
+            .image("image1")
+            .set(&mut contract);
+        contract.purge_contract(accounts(3), None).unwrap();
+
+        assert_eq!(
+            contract.get_stats(),
+            Stats {
+                total_contracts: 2,
+                per_lang: vec![(Lang::Rust, 1), (Lang::AssemblyScript, 1)],
+                per_builder_image: vec![("image1".to_string(), 2)],
+            }
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::challenge::MAX_REASON_LEN;
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, StatusChangedData};
+use crate::{AdminLogAction, ChangeKind, SourceScan};
+
+/// Where a stored verification stands relative to the code deployed on the account.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum VerificationStatus {
+    /// Stored, but fewer than `Config::required_attestations` verifiers have attested
+    /// its code so far.
+    Pending,
+    /// The sources matched the deployed code when `set_contract` was called.
+    Verified,
+    /// The account has since deployed code that was not verified.
+    Stale,
+    /// The verification was found to be wrong and must not be trusted.
+    Revoked {
+        reason: String,
+        revoked_by: AccountId,
+        revoked_at: U64,
+    },
+    /// Verified more than `Config::verification_ttl_epochs` ago and not renewed since.
+    /// Only reported by views; the stored status stays `Verified`.
+    Expired,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Records a check of the code hash `account_id` currently has deployed. A verified
+    /// entry whose account runs different code is flagged `Stale`; a stale entry whose
+    /// account is back on the verified code is restored to `Verified`.
+    #[handle_result]
+    pub fn mark_stale(
+        &mut self,
+        account_id: AccountId,
+        current_hash: String,
+    ) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        let current_hash = self.check_valid_code_hash(&current_hash)?;
+
+        let mut contract = self
+            .read_contract(&account_id)
+            .ok_or_else(|| SourceScanError::no_contract(&account_id))?;
+        ensure!(
+            matches!(
+                contract.status,
+                VerificationStatus::Verified | VerificationStatus::Stale
+            ),
+            SourceScanError::InvalidState("Only verified entries can be marked stale".to_string())
+        );
+
+        let matches = current_hash == contract.code_hash;
+        contract.last_checked_at = Some(U64(env::block_timestamp()));
+        contract.onchain_hash_at_check = Some(current_hash);
+        self.store_contract(&account_id, &contract);
+
+        match (contract.status, matches) {
+            (VerificationStatus::Verified, false) => {
+                self.set_status(&account_id, VerificationStatus::Stale)
+            }
+            (VerificationStatus::Stale, true) => {
+                self.set_status(&account_id, VerificationStatus::Verified)
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Marks the entry as revoked while keeping it and its history, unlike `purge_contract`.
+    #[handle_result]
+    pub fn revoke_contract(
+        &mut self,
+        account_id: AccountId,
+        reason: String,
+    ) -> Result<(), SourceScanError> {
+        self.check_verifier()?;
+        self.check_not_paused()?;
+        ensure!(
+            !reason.trim().is_empty(),
+            SourceScanError::InvalidInput("Revocation reason is required".to_string())
+        );
+        ensure!(
+            reason.len() <= MAX_REASON_LEN,
+            SourceScanError::ContentTooLong(format!(
+                "Revocation reason must be at most {} bytes",
+                MAX_REASON_LEN
+            ))
+        );
+        ensure!(
+            !self.status_of(&account_id)?.is_revoked(),
+            SourceScanError::AlreadyExists("Entry is already revoked".to_string())
+        );
+
+        self.revoke(&account_id, reason);
+        Ok(())
+    }
+}
+
+impl VerificationStatus {
+    pub fn is_revoked(&self) -> bool {
+        matches!(self, VerificationStatus::Revoked { .. })
+    }
+}
+
+impl SourceScan {
+    fn status_of(&self, account_id: &AccountId) -> Result<VerificationStatus, SourceScanError> {
+        self.read_contract(account_id)
+            .map(|contract| contract.status)
+            .ok_or_else(|| SourceScanError::no_contract(account_id))
+    }
+
+    /// Records who revoked the entry and when; the caller checks the entry is not revoked yet.
+    pub(crate) fn revoke(&mut self, account_id: &AccountId, reason: String) {
+        self.set_status(
+            account_id,
+            VerificationStatus::Revoked {
+                reason,
+                revoked_by: env::predecessor_account_id(),
+                revoked_at: U64(env::block_timestamp()),
+            },
+        );
+        self.notify_watchers(account_id, ChangeKind::StatusChanged);
+        self.burn_badge(account_id);
+    }
+
+    /// Every transition is also kept in the entry's history as an audit trail.
+    pub(crate) fn set_status(&mut self, account_id: &AccountId, status: VerificationStatus) {
+        let mut contract = self
+            .read_contract(account_id)
+            .unwrap_or_else(|| env::panic_str(&format!("No verified contract for {}", account_id)));
+        contract.status = status.clone();
+        self.touch_contract(account_id, &mut contract);
+        self.store_contract(account_id, &contract);
+        self.push_history(account_id, contract);
+        self.record_change(account_id, ChangeKind::StatusChanged);
+        self.log_admin(AdminLogAction::SetStatus {
+            account_id: account_id.clone(),
+            status: status.clone(),
+        });
+
+        Event::StatusChanged(vec![StatusChangedData {
+            account_id: account_id.clone(),
+            status,
+        }])
+        .emit();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{code_hash, get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn status(contract: &SourceScan) -> VerificationStatus {
+        contract.get_contract(accounts(1)).unwrap().status
+    }
+
+    #[test]
+    fn status_transitions() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        assert_eq!(status(&contract), VerificationStatus::Verified);
+
+        contract
+            .mark_stale(accounts(1), code_hash("code_hash"))
+            .unwrap();
+        assert_eq!(status(&contract), VerificationStatus::Verified);
+        contract
+            .mark_stale(accounts(1), code_hash("redeployed"))
+            .unwrap();
+        assert_eq!(status(&contract), VerificationStatus::Stale);
+        let entry = contract.get_contract(accounts(1)).unwrap();
+        assert_eq!(entry.onchain_hash_at_check, Some(code_hash("redeployed")));
+        assert_eq!(entry.last_checked_at, Some(U64(0)));
+
+        testing_env!(get_context(accounts(0)).block_timestamp(100).build());
+        contract
+            .revoke_contract(accounts(1), "Wrong entry point".to_string())
+            .unwrap();
+        assert_eq!(
+            status(&contract),
+            VerificationStatus::Revoked {
+                reason: "Wrong entry point".to_string(),
+                revoked_by: accounts(0),
+                revoked_at: U64(100),
+            }
+        );
+        let (history, _, _) = contract.get_contract_history(accounts(1), 0, 10);
+        assert_eq!(history.len(), 3);
+        assert!(history[2].contract.status.is_revoked());
+
+        // Re-verifying restores the entry.
+        set_entry(&mut contract, accounts(1), "code_hash");
+        assert_eq!(status(&contract), VerificationStatus::Verified);
+        assert_eq!(
+            contract.get_changes_since(0, 10)[1].2,
+            ChangeKind::StatusChanged
+        );
+    }
+
+    #[test]
+    fn revoked_entry_cannot_become_stale() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        contract
+            .revoke_contract(accounts(1), "Wrong entry point".to_string())
+            .unwrap();
+        assert_eq!(
+            contract.mark_stale(accounts(1), code_hash("redeployed")),
+            Err(SourceScanError::InvalidState(
+                "Only verified entries can be marked stale".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn revocation_reason_is_capped() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        assert_eq!(
+            contract.revoke_contract(accounts(1), "x".repeat(MAX_REASON_LEN + 1)),
+            Err(SourceScanError::ContentTooLong(
+                "Revocation reason must be at most 1024 bytes".to_string()
+            ))
+        );
+        contract
+            .revoke_contract(accounts(1), "x".repeat(MAX_REASON_LEN))
+            .unwrap();
+    }
+
+    #[test]
+    fn others_cannot_revoke() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        testing_env!(get_context(accounts(1)).build());
+        assert_eq!(
+            contract.revoke_contract(accounts(1), "Wrong entry point".to_string()),
+            Err(SourceScanError::Unauthorized(
+                "Only owner or verifier can call this method".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn stale_entry_recovers_on_matching_check() {
+        testing_env!(get_context(accounts(0)).block_timestamp(100).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        contract
+            .mark_stale(accounts(1), code_hash("redeployed"))
+            .unwrap();
+        contract
+            .mark_stale(accounts(1), code_hash("code_hash"))
+            .unwrap();
+        assert_eq!(status(&contract), VerificationStatus::Verified);
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().last_checked_at,
+            Some(U64(100))
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use near_sdk::{env, NearToken, Promise, StorageUsage};
+
+use crate::error::{ensure, SourceScanError};
+
+fn storage_cost(bytes: StorageUsage) -> NearToken {
+    env::storage_byte_cost().saturating_mul(bytes as u128)
+}
+
+/// Checks the attached deposit covers the storage the call added since
+/// `initial_storage`, and refunds the rest to the caller. For methods any account can
+/// call, so their writes are paid for by the caller rather than the registry.
+pub(crate) fn charge_storage(initial_storage: StorageUsage) -> Result<(), SourceScanError> {
+    let cost = storage_cost(env::storage_usage().saturating_sub(initial_storage));
+    let deposit = env::attached_deposit();
+    ensure!(
+        deposit >= cost,
+        SourceScanError::InsufficientDeposit(format!("Attach {} to cover storage", cost))
+    );
+
+    let refund = deposit.saturating_sub(cost);
+    if !refund.is_zero() {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+    Ok(())
+}
+
+/// Returns the deposit for the storage the call freed since `initial_storage` to the
+/// caller.
+pub(crate) fn refund_storage(initial_storage: StorageUsage) {
+    let refund = storage_cost(initial_storage.saturating_sub(env::storage_usage()));
+    if !refund.is_zero() {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+}
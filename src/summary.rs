@@ -0,0 +1,125 @@
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::advisory::AdvisoryTarget;
+use crate::{ContractData, Lang, SortBy, SourceScan, VerificationStatus};
+
+/// Compact view of a verified entry for list pages. Build metadata, source and the
+/// attached tags, audits, attesters and advisories are left out; only their counts are
+/// returned.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSummary {
+    pub cid: String,
+    pub lang: Lang,
+    pub code_hash: String,
+    pub status: VerificationStatus,
+    pub updated_at: U64,
+    pub tag_count: u32,
+    pub audit_count: u32,
+    pub attester_count: u32,
+    /// Active advisories for the account or its code hash.
+    pub active_advisory_count: u32,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    pub fn get_contract_summary(&self, account_id: AccountId) -> Option<ContractSummary> {
+        let contract = self.view_contract(&account_id)?;
+        Some(self.summarize(&account_id, contract))
+    }
+
+    /// Same paging, ordering and counts as `get_contracts`, returning summaries.
+    pub fn get_contract_summaries(
+        &self,
+        from_index: usize,
+        limit: usize,
+        sort_by: Option<SortBy>,
+    ) -> (Vec<(AccountId, ContractSummary)>, u64, u64) {
+        let (contracts, pages, total_count) = self.get_contracts(from_index, limit, sort_by);
+        let summaries = contracts
+            .into_iter()
+            .map(|(account_id, contract)| {
+                let summary = self.summarize(&account_id, contract);
+                (account_id, summary)
+            })
+            .collect();
+
+        (summaries, pages, total_count)
+    }
+}
+
+impl SourceScan {
+    fn summarize(&self, account_id: &AccountId, contract: ContractData) -> ContractSummary {
+        let count = |len: usize| len as u32;
+
+        let mut advisory_ids = self
+            .advisory_index
+            .get(&AdvisoryTarget::Account(account_id.clone()))
+            .unwrap_or_default();
+        advisory_ids.extend(
+            self.advisory_index
+                .get(&AdvisoryTarget::CodeHash(contract.code_hash.clone()))
+                .unwrap_or_default(),
+        );
+        let active_advisories = advisory_ids
+            .iter()
+            .filter(|id| self.active_advisories.contains(id))
+            .count();
+
+        ContractSummary {
+            cid: contract.cid,
+            lang: contract.lang,
+            code_hash: contract.code_hash,
+            status: contract.status,
+            updated_at: contract.updated_at,
+            tag_count: count(self.contract_tags.get(account_id).map_or(0, |t| t.len())),
+            audit_count: count(self.audits.get(account_id).map_or(0, |a| a.len())),
+            attester_count: count(self.attesters.get(account_id).map_or(0, |a| a.len())),
+            active_advisory_count: count(active_advisories),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{cid, code_hash, get_context, set_entry};
+    use crate::Severity;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn summary_counts_attached_data() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        for account_id in [accounts(1), accounts(2)] {
+            set_entry(&mut contract, account_id.clone(), account_id.as_str());
+        }
+        contract.add_tag(accounts(1), "defi".to_string()).unwrap();
+        contract.add_tag(accounts(1), "amm".to_string()).unwrap();
+        contract
+            .publish_advisory(
+                AdvisoryTarget::CodeHash(code_hash(accounts(1).as_str())),
+                Severity::High,
+                cid("advisory"),
+            )
+            .unwrap();
+
+        let summary = contract.get_contract_summary(accounts(1)).unwrap();
+        assert_eq!(summary.code_hash, code_hash(accounts(1).as_str()));
+        assert_eq!(summary.tag_count, 2);
+        assert_eq!(summary.audit_count, 0);
+        assert_eq!(summary.attester_count, 1);
+        assert_eq!(summary.active_advisory_count, 1);
+
+        let (summaries, pages, total_count) =
+            contract.get_contract_summaries(0, 1, Some(SortBy::Name));
+        assert_eq!(pages, 2);
+        assert_eq!(total_count, 2);
+        assert_eq!(summaries, vec![(accounts(1), summary)]);
+        assert!(contract.get_contract_summary(accounts(3)).is_none());
+    }
+}
@@ -0,0 +1,196 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+use std::ops::Bound;
+
+use crate::{ContractData, SourceScan};
+
+/// What happened to an entry at a given change sequence number.
+#[derive(
+    Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum ChangeKind {
+    /// The entry was created or re-verified; fetch it with `get_contract`.
+    Set,
+    Purged,
+    /// The entry's `status` changed without a new verification.
+    StatusChanged,
+    RiskFlagsChanged,
+    /// A verifier renewed the entry before it expired.
+    Renewed,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+pub(crate) struct ChangeRecord {
+    account_id: AccountId,
+    kind: ChangeKind,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Returns up to `limit` changes with a sequence number greater than `seq`, oldest
+    /// first. Sequence numbers start at 1, so indexers can start from `seq = 0` and
+    /// resume from the last sequence number they processed.
+    pub fn get_changes_since(&self, seq: u64, limit: u64) -> Vec<(u64, AccountId, ChangeKind)> {
+        (seq..self.changes.len())
+            .take(self.page_limit(limit as usize))
+            .filter_map(|index| {
+                let change = self.changes.get(index)?;
+                Some((index + 1, change.account_id, change.kind))
+            })
+            .collect()
+    }
+
+    /// Pages through the change log newest first, skipping the latest `from_index`
+    /// changes. Returns the same tuples as `get_changes_since`.
+    pub fn get_recent_activity(
+        &self,
+        from_index: u64,
+        limit: u64,
+    ) -> Vec<(u64, AccountId, ChangeKind)> {
+        (0..self.changes.len().saturating_sub(from_index))
+            .rev()
+            .take(self.page_limit(limit as usize))
+            .filter_map(|index| {
+                let change = self.changes.get(index)?;
+                Some((index + 1, change.account_id, change.kind))
+            })
+            .collect()
+    }
+
+    /// Entries whose `updated_at` is at or after `timestamp`, least recently updated
+    /// first, so mirrors can refresh only what changed since their last sync. Accounts
+    /// purged since then are listed with `None` at the time of the purge.
+    pub fn get_contracts_updated_since(
+        &self,
+        timestamp: U64,
+        from_index: usize,
+        limit: usize,
+    ) -> Vec<(AccountId, Option<ContractData>)> {
+        let limit = self.page_limit(limit);
+        self.updated_at_index
+            .range((Bound::Included((timestamp.0, 0)), Bound::Unbounded))
+            .skip(from_index)
+            .take(limit)
+            .map(|(_, account_id)| {
+                let contract = self.view_contract(&account_id);
+                (account_id, contract)
+            })
+            .collect()
+    }
+}
+
+impl SourceScan {
+    pub(crate) fn record_change(&mut self, account_id: &AccountId, kind: ChangeKind) {
+        self.changes.push(&ChangeRecord {
+            account_id: account_id.clone(),
+            kind,
+        });
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn changes_are_sequenced() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        set_entry(&mut contract, accounts(1), "code_hash");
+        set_entry(&mut contract, accounts(2), "code_hash");
+        set_entry(&mut contract, accounts(1), "code_hash");
+        contract
+            .purge_contracts(vec![accounts(2), accounts(3)], None)
+            .unwrap();
+
+        assert_eq!(
+            contract.get_changes_since(0, 10),
+            vec![
+                (1, accounts(1), ChangeKind::Set),
+                (2, accounts(2), ChangeKind::Set),
+                (3, accounts(1), ChangeKind::Set),
+                (4, accounts(2), ChangeKind::Purged),
+            ]
+        );
+        assert_eq!(
+            contract.get_changes_since(2, 1),
+            vec![(3, accounts(1), ChangeKind::Set)]
+        );
+        assert!(contract.get_changes_since(4, 10).is_empty());
+        assert!(contract.get_changes_since(100, 10).is_empty());
+
+        assert_eq!(
+            contract.get_recent_activity(0, 2),
+            vec![
+                (4, accounts(2), ChangeKind::Purged),
+                (3, accounts(1), ChangeKind::Set),
+            ]
+        );
+        assert_eq!(
+            contract.get_recent_activity(3, 10),
+            vec![(1, accounts(1), ChangeKind::Set)]
+        );
+        assert!(contract.get_recent_activity(4, 10).is_empty());
+    }
+
+    #[test]
+    fn contracts_updated_since_timestamp() {
+        testing_env!(get_context(accounts(0)).block_timestamp(100).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        set_entry(&mut contract, accounts(2), "code_hash");
+
+        testing_env!(get_context(accounts(0)).block_timestamp(200).build());
+        set_entry(&mut contract, accounts(3), "code_hash");
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        let ids = |entries: Vec<(AccountId, Option<ContractData>)>| {
+            entries
+                .into_iter()
+                .map(|(account_id, contract)| (account_id, contract.is_some()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(contract.get_contracts_updated_since(U64(150), 0, 10)),
+            vec![(accounts(3), true), (accounts(1), true)]
+        );
+        assert_eq!(
+            ids(contract.get_contracts_updated_since(U64(0), 1, 2)),
+            vec![(accounts(3), true), (accounts(1), true)]
+        );
+
+        contract.purge_contract(accounts(3), None).unwrap();
+        assert_eq!(
+            ids(contract.get_contracts_updated_since(U64(200), 0, 10)),
+            vec![(accounts(1), true), (accounts(3), false)]
+        );
+        assert!(contract
+            .get_contracts_updated_since(U64(201), 0, 10)
+            .is_empty());
+
+        testing_env!(get_context(accounts(0)).block_timestamp(300).build());
+        contract
+            .revoke_contract(accounts(2), "Wrong entry point".to_string())
+            .unwrap();
+        set_entry(&mut contract, accounts(3), "code_hash");
+        assert_eq!(
+            ids(contract.get_contracts_updated_since(U64(200), 0, 10)),
+            vec![
+                (accounts(1), true),
+                (accounts(2), true),
+                (accounts(3), true)
+            ]
+        );
+    }
+}
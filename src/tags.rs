@@ -0,0 +1,230 @@
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, TagChangedData};
+use crate::indexes::{index_insert, index_remove, key_hash};
+use crate::roles::Role;
+use crate::{ContractData, SourceScan, StorageKey};
+
+/// Tags are stored lowercase and limited to `[a-z0-9-]` so "DeFi" and "defi" match.
+fn normalize_tag(tag: &str, max_len: u32) -> Result<String, SourceScanError> {
+    let tag = tag.trim().to_lowercase();
+    let message = format!(
+        "Invalid tag {:?}: use up to {} characters from a-z, 0-9 and -",
+        tag, max_len
+    );
+    ensure!(
+        tag.len() <= max_len as usize,
+        SourceScanError::ContentTooLong(message)
+    );
+    ensure!(
+        !tag.is_empty()
+            && tag
+                .bytes()
+                .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-')),
+        SourceScanError::InvalidInput(message)
+    );
+    Ok(tag)
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Tags the entry for `account_id`. Callable by verifiers and by the account itself.
+    #[handle_result]
+    pub fn add_tag(&mut self, account_id: AccountId, tag: String) -> Result<(), SourceScanError> {
+        self.check_can_tag(&account_id)?;
+        let tag = normalize_tag(&tag, self.config.max_tag_len)?;
+
+        let mut tags = self.contract_tags.get(&account_id).unwrap_or_default();
+        if tags.contains(&tag) {
+            return Ok(());
+        }
+        ensure!(
+            tags.len() < self.config.max_tags as usize,
+            SourceScanError::ContentTooLong(format!(
+                "An entry can carry at most {} tags",
+                self.config.max_tags
+            ))
+        );
+
+        index_insert(&mut self.tag_index, &tag, &account_id, || {
+            StorageKey::TagIndexEntries {
+                tag: key_hash(&tag),
+            }
+        });
+        tags.push(tag.clone());
+        self.contract_tags.insert(&account_id, &tags);
+
+        Event::TagAdded(vec![TagChangedData { account_id, tag }]).emit();
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn remove_tag(
+        &mut self,
+        account_id: AccountId,
+        tag: String,
+    ) -> Result<(), SourceScanError> {
+        self.check_can_tag(&account_id)?;
+        let tag = tag.trim().to_lowercase();
+
+        let mut tags = self.contract_tags.get(&account_id).unwrap_or_default();
+        let Some(position) = tags.iter().position(|existing| *existing == tag) else {
+            return Ok(());
+        };
+
+        index_remove(&mut self.tag_index, &tag, &account_id);
+        tags.remove(position);
+        if tags.is_empty() {
+            self.contract_tags.remove(&account_id);
+        } else {
+            self.contract_tags.insert(&account_id, &tags);
+        }
+
+        Event::TagRemoved(vec![TagChangedData { account_id, tag }]).emit();
+        Ok(())
+    }
+
+    pub fn get_tags(&self, account_id: AccountId) -> Vec<String> {
+        self.contract_tags.get(&account_id).unwrap_or_default()
+    }
+
+    pub fn get_contracts_by_tag(
+        &self,
+        tag: String,
+        from_index: usize,
+        limit: usize,
+    ) -> (Vec<(AccountId, ContractData)>, u64, u64) {
+        let tag = tag.trim().to_lowercase();
+        self.page_index(&self.tag_index, &tag, from_index, limit)
+    }
+}
+
+impl SourceScan {
+    fn check_can_tag(&self, account_id: &AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        let predecessor = env::predecessor_account_id();
+        ensure!(
+            predecessor == *account_id || self.has_role(&predecessor, Role::Verifier),
+            SourceScanError::Unauthorized(
+                "Only owner, verifier or the contract account can change tags".to_string()
+            )
+        );
+        ensure!(
+            self.contracts.get(account_id).is_some(),
+            SourceScanError::no_contract(account_id)
+        );
+        Ok(())
+    }
+
+    /// Drops every tag of a purged entry.
+    pub(crate) fn clear_tags(&mut self, account_id: &AccountId) {
+        for tag in self.contract_tags.remove(account_id).unwrap_or_default() {
+            index_remove(&mut self.tag_index, &tag, account_id);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn tagged(contract: &SourceScan, tag: &str) -> Vec<AccountId> {
+        let (entries, _, _) = contract.get_contracts_by_tag(tag.to_string(), 0, 10);
+        entries
+            .into_iter()
+            .map(|(account_id, _)| account_id)
+            .collect()
+    }
+
+    #[test]
+    fn tags_are_indexed() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+        set_entry(&mut contract, accounts(2), "code_hash");
+
+        contract.add_tag(accounts(1), "DeFi".to_string()).unwrap();
+        contract.add_tag(accounts(1), "bridge".to_string()).unwrap();
+        contract.add_tag(accounts(2), "defi".to_string()).unwrap();
+        assert_eq!(contract.get_tags(accounts(1)), vec!["defi", "bridge"]);
+        assert_eq!(tagged(&contract, "defi"), vec![accounts(1), accounts(2)]);
+
+        // Re-verification keeps tags.
+        set_entry(&mut contract, accounts(1), "code_hash");
+        contract
+            .remove_tag(accounts(1), "defi".to_string())
+            .unwrap();
+        assert_eq!(tagged(&contract, "defi"), vec![accounts(2)]);
+        assert_eq!(tagged(&contract, "bridge"), vec![accounts(1)]);
+
+        contract.purge_contract(accounts(1), None).unwrap();
+        assert!(tagged(&contract, "bridge").is_empty());
+        assert!(contract.get_tags(accounts(1)).is_empty());
+    }
+
+    #[test]
+    fn contract_account_can_tag_itself() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.add_tag(accounts(1), "nft".to_string()).unwrap();
+        assert_eq!(tagged(&contract, "nft"), vec![accounts(1)]);
+    }
+
+    #[test]
+    fn others_cannot_tag() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        testing_env!(get_context(accounts(2)).build());
+        assert_eq!(
+            contract.add_tag(accounts(1), "nft".to_string()),
+            Err(SourceScanError::Unauthorized(
+                "Only owner, verifier or the contract account can change tags".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_tags() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        assert_eq!(
+            contract.add_tag(accounts(1), "de fi".to_string()),
+            Err(SourceScanError::InvalidInput(
+                "Invalid tag \"de fi\": use up to 32 characters from a-z, 0-9 and -".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn tag_limit_follows_config() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_config(crate::Config {
+                max_tags: 1,
+                ..crate::Config::default()
+            })
+            .unwrap();
+        set_entry(&mut contract, accounts(1), "code_hash");
+
+        contract.add_tag(accounts(1), "defi".to_string()).unwrap();
+        assert_eq!(
+            contract.add_tag(accounts(1), "nft".to_string()),
+            Err(SourceScanError::ContentTooLong(
+                "An entry can carry at most 1 tags".to_string()
+            ))
+        );
+    }
+}
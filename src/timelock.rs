@@ -0,0 +1,261 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{ActionData, Event};
+use crate::governance::Council;
+use crate::{AdminLogAction, Config, SourceScan};
+
+/// Admin actions that must wait out `Config::admin_delay` once a delay is configured.
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+#[borsh(crate = "near_sdk::borsh")]
+pub enum AdminAction {
+    SetOwner {
+        owner_id: AccountId,
+    },
+    EnableGovernance {
+        council: Council,
+    },
+    /// Needed to lower `admin_delay`; other config changes apply directly.
+    SetConfig {
+        config: Config,
+    },
+    PurgeContracts {
+        account_ids: Vec<AccountId>,
+        keep_history: Option<bool>,
+    },
+}
+
+#[derive(Serialize, Deserialize, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct ScheduledAction {
+    pub action: AdminAction,
+    pub scheduled_by: AccountId,
+    /// Block timestamp from which the action can be executed.
+    pub ready_at: U64,
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Queues `action` to run once the configured admin delay has passed, returning its id.
+    #[handle_result]
+    pub fn schedule_action(&mut self, action: AdminAction) -> Result<u64, SourceScanError> {
+        self.check_can_run(&action)?;
+
+        let action_id = self.action_seq;
+        self.action_seq += 1;
+        let scheduled = ScheduledAction {
+            action,
+            scheduled_by: env::predecessor_account_id(),
+            ready_at: U64(env::block_timestamp() + self.config.admin_delay.0),
+        };
+        self.scheduled_actions.insert(&action_id, &scheduled);
+        self.log_admin(AdminLogAction::ScheduleAction {
+            action_id,
+            action: scheduled.action.clone(),
+        });
+
+        Event::ActionScheduled(vec![ActionData {
+            action_id,
+            action: scheduled.action,
+            ready_at: scheduled.ready_at,
+        }])
+        .emit();
+
+        Ok(action_id)
+    }
+
+    /// Drops a scheduled action. Callable by the owner or whoever scheduled it.
+    #[handle_result]
+    pub fn cancel_action(&mut self, action_id: u64) -> Result<(), SourceScanError> {
+        let scheduled = self.scheduled_action(action_id)?;
+        let predecessor = env::predecessor_account_id();
+        ensure!(
+            predecessor == self.owner_id || predecessor == scheduled.scheduled_by,
+            SourceScanError::Unauthorized(
+                "Only owner or the scheduler can cancel this action".to_string()
+            )
+        );
+
+        self.scheduled_actions.remove(&action_id);
+        self.log_admin(AdminLogAction::CancelAction { action_id });
+
+        Event::ActionCancelled(vec![ActionData {
+            action_id,
+            action: scheduled.action,
+            ready_at: scheduled.ready_at,
+        }])
+        .emit();
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn execute_action(&mut self, action_id: u64) -> Result<(), SourceScanError> {
+        let scheduled = self.scheduled_action(action_id)?;
+        self.check_can_run(&scheduled.action)?;
+        ensure!(
+            env::block_timestamp() >= scheduled.ready_at.0,
+            SourceScanError::Timelocked("Action is still timelocked".to_string())
+        );
+
+        self.scheduled_actions.remove(&action_id);
+
+        match scheduled.action {
+            AdminAction::SetOwner { owner_id } => self.change_owner(owner_id),
+            AdminAction::EnableGovernance { council } => self.start_governance(council)?,
+            AdminAction::SetConfig { config } => self.apply_config(config)?,
+            AdminAction::PurgeContracts {
+                account_ids,
+                keep_history,
+            } => self.purge(account_ids, keep_history.unwrap_or(false)),
+        }
+        Ok(())
+    }
+
+    pub fn get_scheduled_actions(&self) -> Vec<(u64, ScheduledAction)> {
+        self.scheduled_actions.to_vec()
+    }
+}
+
+impl SourceScan {
+    /// Rejects direct calls to timelocked actions while an admin delay is configured.
+    pub(crate) fn check_not_timelocked(&self) -> Result<(), SourceScanError> {
+        ensure!(
+            self.config.admin_delay.0 == 0,
+            SourceScanError::Timelocked(
+                "This action is timelocked; use schedule_action".to_string()
+            )
+        );
+        Ok(())
+    }
+
+    fn check_can_run(&self, action: &AdminAction) -> Result<(), SourceScanError> {
+        match action {
+            AdminAction::PurgeContracts { .. } => {
+                self.check_verifier()?;
+                self.check_not_paused()
+            }
+            _ => self.check_owner(),
+        }
+    }
+
+    fn scheduled_action(&self, action_id: u64) -> Result<ScheduledAction, SourceScanError> {
+        self.scheduled_actions
+            .get(&action_id)
+            .ok_or_else(|| SourceScanError::NotFound("Scheduled action not found".to_string()))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_context;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    fn timelocked() -> SourceScan {
+        testing_env!(get_context(accounts(0)).block_timestamp(DAY).build());
+        let mut contract = SourceScan::new();
+        contract
+            .set_config(Config {
+                admin_delay: U64(DAY),
+                ..Config::default()
+            })
+            .unwrap();
+        contract
+    }
+
+    #[test]
+    fn scheduled_action_runs_after_delay() {
+        let mut contract = timelocked();
+        let action_id = contract
+            .schedule_action(AdminAction::SetOwner {
+                owner_id: accounts(1),
+            })
+            .unwrap();
+        assert_eq!(contract.get_scheduled_actions()[0].1.ready_at, U64(2 * DAY));
+
+        testing_env!(get_context(accounts(0)).block_timestamp(2 * DAY).build());
+        contract.execute_action(action_id).unwrap();
+
+        assert_eq!(contract.get_owner(), accounts(1));
+        assert!(contract.get_scheduled_actions().is_empty());
+    }
+
+    #[test]
+    fn scheduled_action_waits_for_delay() {
+        let mut contract = timelocked();
+        let action_id = contract
+            .schedule_action(AdminAction::SetOwner {
+                owner_id: accounts(1),
+            })
+            .unwrap();
+
+        testing_env!(get_context(accounts(0))
+            .block_timestamp(2 * DAY - 1)
+            .build());
+        assert_eq!(
+            contract.execute_action(action_id),
+            Err(SourceScanError::Timelocked(
+                "Action is still timelocked".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn cancelled_action_cannot_run() {
+        let mut contract = timelocked();
+        contract.add_verifier(accounts(1)).unwrap();
+
+        testing_env!(get_context(accounts(1)).block_timestamp(DAY).build());
+        let action_id = contract
+            .schedule_action(AdminAction::PurgeContracts {
+                account_ids: vec![accounts(2)],
+                keep_history: None,
+            })
+            .unwrap();
+
+        testing_env!(get_context(accounts(0)).block_timestamp(DAY).build());
+        contract.cancel_action(action_id).unwrap();
+
+        testing_env!(get_context(accounts(1)).block_timestamp(2 * DAY).build());
+        assert_eq!(
+            contract.execute_action(action_id),
+            Err(SourceScanError::NotFound(
+                "Scheduled action not found".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn direct_purge_is_rejected() {
+        let mut contract = timelocked();
+
+        assert_eq!(
+            contract.purge_contract(accounts(1), None),
+            Err(SourceScanError::Timelocked(
+                "This action is timelocked; use schedule_action".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn lowering_delay_is_timelocked() {
+        let mut contract = timelocked();
+
+        assert_eq!(
+            contract.set_config(Config::default()),
+            Err(SourceScanError::Timelocked(
+                "Lowering admin_delay is timelocked; use schedule_action".to_string()
+            ))
+        );
+    }
+}
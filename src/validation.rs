@@ -0,0 +1,726 @@
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::{
+    AdminLogAction, BuildInfo, BuildTarget, Lang, Network, RepoData, RepoSource, SourceScan,
+};
+
+const MAX_NETWORK_NAME_LEN: usize = 32;
+
+/// Implicit accounts are the hex-encoded 32-byte ed25519 public key.
+fn is_implicit(account_id: &str) -> bool {
+    account_id.len() == 64
+        && account_id
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Eth-implicit accounts are `0x` followed by a 20-byte hex address.
+fn is_eth_implicit(account_id: &str) -> bool {
+    account_id.len() == 42
+        && account_id.starts_with("0x")
+        && account_id[2..]
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Code hashes are accepted as base58 (as reported by `near view-state`/RPC) or hex,
+/// and must decode to 32 bytes. Returns the base58 form so the same code always has
+/// one key in the indexes and in source records.
+pub(crate) fn normalize_code_hash(code_hash: &str) -> Option<String> {
+    let bytes = if code_hash.len() == 64 && code_hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        (0..64)
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&code_hash[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?
+    } else {
+        near_sdk::bs58::decode(code_hash).into_vec().ok()?
+    };
+
+    (bytes.len() == 32).then(|| near_sdk::bs58::encode(bytes).into_string())
+}
+
+/// Builder images must be pinned by digest (`name@sha256:<hex>`) so a build can be
+/// reproduced from exactly the same image.
+fn is_pinned_image(image: &str) -> bool {
+    image
+        .split_once("@sha256:")
+        .is_some_and(|(name, digest)| !name.is_empty() && is_sha256_hex(digest))
+}
+
+fn is_sha256_hex(digest: &str) -> bool {
+    digest.len() == 64
+        && digest
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Commits must be referenced by the full 40-character hash; short hashes can become
+/// ambiguous as a repository grows.
+fn is_commit_sha(sha: &str) -> bool {
+    sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn is_valid_repo(repo: &RepoData) -> bool {
+    let valid = |name: &str| !name.is_empty() && !name.contains(char::is_whitespace);
+    // GitLab owners can be nested groups, so only the repository name excludes '/'.
+    valid(&repo.owner) && valid(&repo.repo) && !repo.repo.contains('/')
+}
+
+/// Paths are relative to the repository root and may not leave it.
+fn is_valid_repo_path(path: &str) -> bool {
+    path.split('/')
+        .all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+}
+
+fn is_valid_git_ref(git_ref: &str) -> bool {
+    !git_ref.is_empty() && !git_ref.contains(char::is_whitespace)
+}
+
+#[near_bindgen]
+impl SourceScan {
+    /// Allows sub-accounts of `suffix` (e.g. `near` allows `app.near`) in `set_contract`.
+    #[handle_result]
+    pub fn add_allowed_suffix(&mut self, suffix: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        let suffix = suffix.to_string();
+        if !self.allowed_suffixes.contains(&suffix) {
+            self.allowed_suffixes.push(suffix.clone());
+            self.log_admin(AdminLogAction::AddAllowedSuffix { suffix });
+        }
+        Ok(())
+    }
+
+    #[handle_result]
+    pub fn remove_allowed_suffix(&mut self, suffix: AccountId) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+
+        let len = self.allowed_suffixes.len();
+        self.allowed_suffixes
+            .retain(|allowed| allowed != suffix.as_str());
+        if self.allowed_suffixes.len() != len {
+            self.log_admin(AdminLogAction::RemoveAllowedSuffix {
+                suffix: suffix.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn get_allowed_suffixes(&self) -> Vec<String> {
+        self.allowed_suffixes.clone()
+    }
+
+    /// Allows `image` as a `builder_image` in `set_contract`.
+    #[handle_result]
+    pub fn add_builder_image(&mut self, image: String) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        ensure!(
+            is_pinned_image(&image),
+            SourceScanError::InvalidInput(format!(
+                "Builder image {:?} must be pinned by digest: name@sha256:<hex>",
+                image
+            ))
+        );
+
+        if self.builder_images.insert(&image) {
+            self.log_admin(AdminLogAction::AddBuilderImage { image });
+        }
+        Ok(())
+    }
+
+    /// The last image cannot be removed: an empty allowlist would accept any image
+    /// again. Add the replacement first.
+    #[handle_result]
+    pub fn remove_builder_image(&mut self, image: String) -> Result<(), SourceScanError> {
+        self.check_owner()?;
+        ensure!(
+            self.builder_images.len() != 1 || !self.builder_images.contains(&image),
+            SourceScanError::InvalidState(
+                "Cannot remove the last allowed builder image".to_string()
+            )
+        );
+
+        if self.builder_images.remove(&image) {
+            self.log_admin(AdminLogAction::RemoveBuilderImage { image });
+        }
+        Ok(())
+    }
+
+    pub fn get_builder_images(&self) -> Vec<String> {
+        self.builder_images.to_vec()
+    }
+}
+
+impl SourceScan {
+    /// Implicit and top-level accounts are always accepted; named sub-accounts must end
+    /// with an allowed suffix. An empty suffix list disables the check.
+    pub(crate) fn is_allowed_account(&self, account_id: &AccountId) -> bool {
+        let account_id = account_id.as_str();
+
+        if self.allowed_suffixes.is_empty()
+            || is_implicit(account_id)
+            || is_eth_implicit(account_id)
+            || !account_id.contains('.')
+        {
+            return true;
+        }
+
+        self.allowed_suffixes.iter().any(|suffix| {
+            account_id
+                .strip_suffix(suffix.as_str())
+                .is_some_and(|name| name.ends_with('.'))
+        })
+    }
+
+    pub(crate) fn check_allowed_account(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<(), SourceScanError> {
+        ensure!(
+            self.is_allowed_account(account_id),
+            SourceScanError::InvalidInput(format!(
+                "Account {} does not end with an allowed suffix: {}",
+                account_id,
+                self.allowed_suffixes.join(", ")
+            ))
+        );
+        Ok(())
+    }
+
+    /// `Lang::Other` is reserved for languages without a variant, so spellings of a
+    /// known language cannot fragment the index.
+    pub(crate) fn check_valid_lang(&self, lang: &Lang) -> Result<(), SourceScanError> {
+        if let Lang::Other(name) = lang {
+            ensure!(
+                !name.is_empty() && name.trim() == name && Lang::known(name).is_none(),
+                SourceScanError::InvalidInput(format!(
+                    "Invalid lang {:?}: use the matching Lang variant",
+                    name
+                ))
+            );
+        }
+        Ok(())
+    }
+
+    /// Custom network names must not alias the built-in networks.
+    pub(crate) fn check_valid_network(&self, network: &Network) -> Result<(), SourceScanError> {
+        if let Network::Custom(name) = network {
+            ensure!(
+                !name.is_empty()
+                    && name.len() <= MAX_NETWORK_NAME_LEN
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                    && name != "mainnet"
+                    && name != "testnet",
+                SourceScanError::InvalidInput(format!(
+                    "Invalid network {:?}: expected lowercase letters, digits and dashes",
+                    name
+                ))
+            );
+        }
+        Ok(())
+    }
+
+    /// An empty allowlist disables the check, as with allowed suffixes. Once an image is
+    /// added the allowlist cannot be emptied again.
+    pub(crate) fn check_allowed_builder_image(
+        &self,
+        builder_image: &str,
+    ) -> Result<(), SourceScanError> {
+        ensure!(
+            self.builder_images.is_empty()
+                || self.builder_images.contains(&builder_image.to_string()),
+            SourceScanError::InvalidInput(format!(
+                "Builder image {:?} is not on the allowlist",
+                builder_image
+            ))
+        );
+        Ok(())
+    }
+
+    /// Returns the canonical base58 form of `code_hash`; compare and index only that.
+    pub(crate) fn check_valid_code_hash(&self, code_hash: &str) -> Result<String, SourceScanError> {
+        normalize_code_hash(code_hash).ok_or_else(|| SourceScanError::invalid_code_hash(code_hash))
+    }
+
+    pub(crate) fn check_valid_build_targets(
+        &self,
+        build_targets: &[BuildTarget],
+    ) -> Result<(), SourceScanError> {
+        ensure!(
+            !build_targets.is_empty(),
+            SourceScanError::InvalidInput("At least one build target is required".to_string())
+        );
+        for target in build_targets {
+            ensure!(
+                !target.entry_point.trim().is_empty(),
+                SourceScanError::InvalidInput(
+                    "Build target entry point must not be empty".to_string()
+                )
+            );
+            ensure!(
+                !target
+                    .crate_name
+                    .as_ref()
+                    .is_some_and(|name| name.trim().is_empty()),
+                SourceScanError::InvalidInput(
+                    "Build target crate name must not be empty".to_string()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// The image digest must match `builder_image` when that is pinned, so both fields
+    /// describe the same image.
+    pub(crate) fn check_valid_build_info(
+        &self,
+        build_info: &BuildInfo,
+        builder_image: &str,
+    ) -> Result<(), SourceScanError> {
+        let digest = build_info.image_digest.strip_prefix("sha256:");
+        ensure!(
+            digest.is_some_and(is_sha256_hex),
+            SourceScanError::InvalidInput(format!(
+                "Invalid image digest {:?}: expected sha256:<64 hex characters>",
+                build_info.image_digest
+            ))
+        );
+        if let Some((_, pinned)) = builder_image.split_once("@sha256:") {
+            ensure!(
+                digest == Some(pinned),
+                SourceScanError::InvalidInput(
+                    "Image digest does not match builder_image".to_string()
+                )
+            );
+        }
+        ensure!(
+            !build_info.build_command.is_empty(),
+            SourceScanError::InvalidInput("Build command must not be empty".to_string())
+        );
+        ensure!(
+            !build_info.source_checksum.trim().is_empty(),
+            SourceScanError::InvalidInput("Source checksum must not be empty".to_string())
+        );
+        Ok(())
+    }
+
+    pub(crate) fn check_valid_source(&self, source: &RepoSource) -> Result<(), SourceScanError> {
+        ensure!(
+            is_commit_sha(source.sha()),
+            SourceScanError::InvalidInput(format!(
+                "Invalid commit sha {:?}: expected 40 hex characters",
+                source.sha()
+            ))
+        );
+        match source {
+            RepoSource::Github(repo) | RepoSource::Gitlab(repo) | RepoSource::Codeberg(repo) => {
+                ensure!(
+                    is_valid_repo(repo),
+                    SourceScanError::InvalidInput(format!(
+                        "Invalid repository {:?}/{:?}",
+                        repo.owner, repo.repo
+                    ))
+                );
+            }
+            RepoSource::Git(git) => {
+                ensure!(
+                    git.url.starts_with("https://") && !git.url.contains(char::is_whitespace),
+                    SourceScanError::InvalidInput(format!(
+                        "Invalid git URL {:?}: expected an https:// URL",
+                        git.url
+                    ))
+                );
+            }
+        }
+        if let Some(path) = source.path() {
+            ensure!(
+                is_valid_repo_path(path),
+                SourceScanError::InvalidInput(format!(
+                    "Invalid repository path {:?}: expected a relative path without '.' or '..'",
+                    path
+                ))
+            );
+        }
+        if let Some(git_ref) = source.git_ref() {
+            ensure!(
+                is_valid_git_ref(git_ref),
+                SourceScanError::InvalidInput(format!("Invalid git ref {:?}", git_ref))
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{code_hash, entry, get_context};
+    use crate::{BuildInfo, BuildTarget, GitData};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{env, testing_env};
+
+    fn allowed(contract: &SourceScan, account_id: &str) -> bool {
+        contract.is_allowed_account(&account_id.parse().unwrap())
+    }
+
+    #[test]
+    fn suffix_rules() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        assert!(allowed(&contract, "anything.neer"));
+
+        contract
+            .add_allowed_suffix("near".parse().unwrap())
+            .unwrap();
+        contract
+            .add_allowed_suffix("testnet".parse().unwrap())
+            .unwrap();
+        contract
+            .add_allowed_suffix("near".parse().unwrap())
+            .unwrap();
+        assert_eq!(contract.get_allowed_suffixes(), vec!["near", "testnet"]);
+
+        assert!(allowed(&contract, "app.near"));
+        assert!(allowed(&contract, "v2.app.testnet"));
+        assert!(allowed(&contract, "aurora"));
+        assert!(allowed(
+            &contract,
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
+        ));
+        assert!(allowed(
+            &contract,
+            "0x32400084c286cf3e17e7b677ea9583e60a000324"
+        ));
+        assert!(!allowed(&contract, "app.neer"));
+        assert!(!allowed(&contract, "v1.signer"));
+        assert!(!allowed(&contract, "appnear.io"));
+
+        contract
+            .add_allowed_suffix("signer".parse().unwrap())
+            .unwrap();
+        assert!(allowed(&contract, "v1.signer"));
+
+        contract
+            .remove_allowed_suffix("testnet".parse().unwrap())
+            .unwrap();
+        assert!(!allowed(&contract, "app.testnet"));
+    }
+
+    #[test]
+    fn set_contract_rejects_unknown_suffix() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract
+            .add_allowed_suffix("near".parse().unwrap())
+            .unwrap();
+
+        assert_eq!(
+            entry("app.neer".parse().unwrap(), "code_hash").try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Account app.neer does not end with an allowed suffix: near".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn code_hash_formats() {
+        let hash = code_hash("seed");
+        assert_eq!(normalize_code_hash(&hash), Some(hash));
+        assert_eq!(
+            normalize_code_hash("11111111111111111111111111111111").as_deref(),
+            Some("11111111111111111111111111111111")
+        );
+        assert_eq!(
+            normalize_code_hash("0000000000000000000000000000000000000000000000000000000000000000")
+                .as_deref(),
+            Some("11111111111111111111111111111111")
+        );
+        assert!(normalize_code_hash(
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
+        )
+        .is_some());
+        assert_eq!(normalize_code_hash(""), None);
+        assert_eq!(normalize_code_hash("code_hash"), None);
+        assert_eq!(normalize_code_hash("1111111111111111"), None);
+        assert_eq!(
+            normalize_code_hash("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6"),
+            None
+        );
+    }
+
+    #[test]
+    fn hex_and_base58_code_hashes_share_one_entry() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        let bytes = env::sha256_array(b"seed");
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        let base58 = code_hash("seed");
+
+        entry(accounts(1), "code_hash")
+            .code_hash(hex.clone())
+            .set(&mut contract);
+
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().code_hash,
+            base58
+        );
+        assert_eq!(contract.get_contracts_by_code_hash(hex.clone(), 0, 10).2, 1);
+        assert_eq!(
+            contract.get_contracts_by_code_hash(base58.clone(), 0, 10).0[0].0,
+            accounts(1)
+        );
+        assert!(contract.get_source_record(hex.clone()).is_some());
+
+        contract
+            .set_contracts_for_factory(base58.clone(), vec![accounts(2)])
+            .unwrap();
+        assert_eq!(contract.get_contracts_by_code_hash(base58, 0, 10).2, 2);
+    }
+
+    #[test]
+    fn set_contract_rejects_malformed_code_hash() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .code_hash("hash1")
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Invalid code hash \"hash1\": expected 32 bytes encoded as base58 or hex"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn set_contract_rejects_known_lang_as_other() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .lang(Lang::Other("rust ".to_string()))
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Invalid lang \"rust \": use the matching Lang variant".to_string()
+            ))
+        );
+    }
+
+    const IMAGE: &str = "sourcescan/cargo-near@sha256:bf488476d9c4e49e36862bbdef2c595f88d34a295fd551cc65dc291553849471";
+
+    #[test]
+    fn builder_image_allowlist() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        entry(accounts(1), "code_hash")
+            .image("rust:latest")
+            .set(&mut contract);
+
+        contract.add_builder_image(IMAGE.to_string()).unwrap();
+        assert_eq!(contract.get_builder_images(), vec![IMAGE.to_string()]);
+        entry(accounts(1), "code_hash")
+            .image(IMAGE)
+            .set(&mut contract);
+
+        assert_eq!(
+            contract.remove_builder_image(IMAGE.to_string()),
+            Err(SourceScanError::InvalidState(
+                "Cannot remove the last allowed builder image".to_string()
+            ))
+        );
+        let replacement = IMAGE.replace("bf48", "0000");
+        contract.add_builder_image(replacement.clone()).unwrap();
+        contract.remove_builder_image(IMAGE.to_string()).unwrap();
+        assert_eq!(contract.get_builder_images(), vec![replacement]);
+    }
+
+    #[test]
+    fn set_contract_rejects_unlisted_builder_image() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        contract.add_builder_image(IMAGE.to_string()).unwrap();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .image("rust:latest")
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Builder image \"rust:latest\" is not on the allowlist".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn builder_image_must_be_pinned() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            contract.add_builder_image("sourcescan/cargo-near:latest".to_string()),
+            Err(SourceScanError::InvalidInput(
+                "Builder image \"sourcescan/cargo-near:latest\" must be pinned by digest: \
+                 name@sha256:<hex>"
+                    .to_string()
+            ))
+        );
+    }
+
+    fn repo(owner: &str, repo: &str) -> RepoData {
+        RepoData {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            path: None,
+            git_ref: None,
+        }
+    }
+
+    #[test]
+    fn source_formats() {
+        assert!(is_commit_sha("0123456789ABCDEF0123456789abcdef01234567"));
+        assert!(!is_commit_sha("0123456"));
+        assert!(!is_commit_sha("0123456789abcdef0123456789abcdef0123456g"));
+
+        assert!(is_valid_repo(&repo("near", "core-contracts")));
+        assert!(is_valid_repo(&repo("group/subgroup", "contracts")));
+        assert!(!is_valid_repo(&repo("", "contracts")));
+        assert!(!is_valid_repo(&repo("near", "core contracts")));
+        assert!(!is_valid_repo(&repo("near", "core/contracts")));
+
+        assert!(is_valid_repo_path("contracts/ft"));
+        assert!(!is_valid_repo_path("/contracts"));
+        assert!(!is_valid_repo_path("contracts/../.."));
+        assert!(!is_valid_repo_path("contracts/"));
+        assert!(is_valid_git_ref("v1.0.0"));
+        assert!(!is_valid_git_ref("feature branch"));
+    }
+
+    #[test]
+    fn set_contract_rejects_short_sha() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .source(RepoSource::Gitlab(RepoData {
+                    sha: "main".to_string(),
+                    ..repo("near", "contracts")
+                }))
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Invalid commit sha \"main\": expected 40 hex characters".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn set_contract_rejects_non_https_git_url() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .source(RepoSource::Git(GitData {
+                    url: "git@example.org:contracts.git".to_string(),
+                    sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+                    path: None,
+                    git_ref: None,
+                }))
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Invalid git URL \"git@example.org:contracts.git\": expected an https:// URL"
+                    .to_string()
+            ))
+        );
+    }
+
+    fn build_info(image_digest: &str) -> BuildInfo {
+        BuildInfo {
+            cargo_near_version: Some("0.6.2".to_string()),
+            rustc_version: Some("1.73.0".to_string()),
+            near_sdk_version: Some("5.0.0".to_string()),
+            build_command: vec!["cargo".to_string(), "near".to_string(), "build".to_string()],
+            source_checksum: code_hash("sources"),
+            image_digest: image_digest.to_string(),
+        }
+    }
+
+    #[test]
+    fn build_info_is_stored() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        let digest = IMAGE.split_once('@').unwrap().1;
+
+        entry(accounts(1), "code_hash")
+            .image(IMAGE)
+            .build_info(build_info(digest))
+            .set(&mut contract);
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().build_info,
+            Some(build_info(digest))
+        );
+    }
+
+    #[test]
+    fn build_info_digest_must_match_image() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .image(IMAGE)
+                .build_info(build_info(&format!("sha256:{}", "0".repeat(64))))
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "Image digest does not match builder_image".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn workspace_targets_are_stored() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        let targets = vec![
+            BuildTarget {
+                crate_name: Some("registry".to_string()),
+                entry_point: "src/lib.rs".to_string(),
+                features: vec!["abi".to_string()],
+            },
+            BuildTarget {
+                crate_name: Some("registry-core".to_string()),
+                entry_point: "core/src/lib.rs".to_string(),
+                features: Vec::new(),
+            },
+        ];
+
+        entry(accounts(1), "code_hash")
+            .build_targets(targets.clone())
+            .set(&mut contract);
+        assert_eq!(
+            contract.get_contract(accounts(1)).unwrap().build_targets,
+            targets
+        );
+    }
+
+    #[test]
+    fn set_contract_requires_build_target() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            entry(accounts(1), "code_hash")
+                .build_targets(Vec::new())
+                .try_set(&mut contract),
+            Err(SourceScanError::InvalidInput(
+                "At least one build target is required".to_string()
+            ))
+        );
+    }
+}
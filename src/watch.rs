@@ -0,0 +1,207 @@
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::error::{ensure, SourceScanError};
+use crate::events::{Event, WatchData};
+use crate::indexes::{index_insert, index_remove, key_hash};
+use crate::storage::{charge_storage, refund_storage};
+use crate::{ChangeKind, SourceScan, StorageKey};
+
+/// Most watchers an entry can have, so the `watched_contract_changed` event naming
+/// them all stays well within the log size limit.
+const MAX_WATCHERS: u64 = 100;
+
+#[near_bindgen]
+impl SourceScan {
+    /// Subscribes the caller to re-verifications and revocations of `account_id`. The
+    /// caller pays for the storage with the attached deposit, refunded on unwatching.
+    #[payable]
+    #[handle_result]
+    pub fn watch_contract(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+        ensure!(
+            self.contracts.get(&account_id).is_some(),
+            SourceScanError::no_contract(&account_id)
+        );
+        let watcher = env::predecessor_account_id();
+        let watchers = self.watchers.get(&account_id);
+        if watchers
+            .as_ref()
+            .is_some_and(|watchers| watchers.contains(&watcher))
+        {
+            return charge_storage(env::storage_usage());
+        }
+        ensure!(
+            watchers.map_or(0, |watchers| watchers.len()) < MAX_WATCHERS,
+            SourceScanError::ContentTooLong(format!(
+                "An entry can have at most {} watchers",
+                MAX_WATCHERS
+            ))
+        );
+
+        let initial_storage = env::storage_usage();
+        index_insert(&mut self.watchers, &account_id, &watcher, || {
+            StorageKey::WatchersEntries {
+                account_hash: key_hash(account_id.as_str()),
+            }
+        });
+        index_insert(&mut self.watched, &watcher, &account_id, || {
+            StorageKey::WatchedEntries {
+                account_hash: key_hash(watcher.as_str()),
+            }
+        });
+        charge_storage(initial_storage)
+    }
+
+    /// Still works after the entry is purged, so stale subscriptions can be dropped.
+    #[handle_result]
+    pub fn unwatch_contract(&mut self, account_id: AccountId) -> Result<(), SourceScanError> {
+        self.check_not_paused()?;
+
+        let initial_storage = env::storage_usage();
+        let watcher = env::predecessor_account_id();
+        index_remove(&mut self.watchers, &account_id, &watcher);
+        index_remove(&mut self.watched, &watcher, &account_id);
+        refund_storage(initial_storage);
+        Ok(())
+    }
+
+    pub fn get_watchers(
+        &self,
+        account_id: AccountId,
+        from_index: usize,
+        limit: usize,
+    ) -> Vec<AccountId> {
+        let limit = self.page_limit(limit);
+        self.watchers
+            .get(&account_id)
+            .map(|watchers| watchers.iter().skip(from_index).take(limit).collect())
+            .unwrap_or_default()
+    }
+
+    /// Entries `account_id` is watching.
+    pub fn get_watched(
+        &self,
+        account_id: AccountId,
+        from_index: usize,
+        limit: usize,
+    ) -> Vec<AccountId> {
+        let limit = self.page_limit(limit);
+        self.watched
+            .get(&account_id)
+            .map(|watched| watched.iter().skip(from_index).take(limit).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl SourceScan {
+    /// Emits a `watched_contract_changed` event naming the watchers of `account_id`, so
+    /// notification services don't have to look them up.
+    pub(crate) fn notify_watchers(&self, account_id: &AccountId, kind: ChangeKind) {
+        let Some(watchers) = self.watchers.get(account_id) else {
+            return;
+        };
+
+        Event::WatchedContractChanged(vec![WatchData {
+            account_id: account_id.clone(),
+            kind,
+            watchers: watchers.to_vec(),
+        }])
+        .emit();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, set_entry};
+    use near_sdk::test_utils::{accounts, get_logs};
+    use near_sdk::{testing_env, NearToken};
+
+    fn set(contract: &mut SourceScan, code: &str) {
+        testing_env!(get_context(accounts(0)).build());
+        set_entry(contract, accounts(1), code);
+    }
+
+    fn watch(contract: &mut SourceScan, watcher: AccountId) -> Result<(), SourceScanError> {
+        testing_env!(get_context(watcher)
+            .attached_deposit(NearToken::from_millinear(10))
+            .build());
+        contract.watch_contract(accounts(1))
+    }
+
+    #[test]
+    fn watchers_are_notified() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set(&mut contract, "v1");
+
+        watch(&mut contract, accounts(2)).unwrap();
+        assert_eq!(contract.get_watchers(accounts(1), 0, 10), vec![accounts(2)]);
+        assert_eq!(contract.get_watched(accounts(2), 0, 10), vec![accounts(1)]);
+
+        set(&mut contract, "v2");
+        assert!(get_logs().contains(
+            &r#"EVENT_JSON:{"standard":"sourcescan","version":"1.0.0","event":"watched_contract_changed","data":[{"account_id":"bob","kind":"set","watchers":["charlie"]}]}"#
+                .to_string()
+        ));
+
+        contract
+            .revoke_contract(accounts(1), "wrong sources".to_string())
+            .unwrap();
+        assert_eq!(
+            get_logs().last().unwrap(),
+            r#"EVENT_JSON:{"standard":"sourcescan","version":"1.0.0","event":"watched_contract_changed","data":[{"account_id":"bob","kind":"status_changed","watchers":["charlie"]}]}"#
+        );
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.unwatch_contract(accounts(1)).unwrap();
+        assert!(contract.get_watchers(accounts(1), 0, 10).is_empty());
+        assert!(contract.get_watched(accounts(2), 0, 10).is_empty());
+    }
+
+    #[test]
+    fn watchers_pay_for_storage() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set(&mut contract, "v1");
+
+        testing_env!(get_context(accounts(2)).build());
+        assert!(matches!(
+            contract.watch_contract(accounts(1)),
+            Err(SourceScanError::InsufficientDeposit(_))
+        ));
+    }
+
+    #[test]
+    fn watchers_are_capped() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = SourceScan::new();
+        set(&mut contract, "v1");
+
+        for i in 0..MAX_WATCHERS {
+            watch(&mut contract, format!("watcher{}.near", i).parse().unwrap()).unwrap();
+        }
+        // Watching again is a no-op, not a new watcher.
+        watch(&mut contract, "watcher0.near".parse().unwrap()).unwrap();
+        assert_eq!(
+            watch(&mut contract, accounts(2)),
+            Err(SourceScanError::ContentTooLong(
+                "An entry can have at most 100 watchers".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn cannot_watch_missing_entry() {
+        testing_env!(get_context(accounts(2)).build());
+        let mut contract = SourceScan::new();
+
+        assert_eq!(
+            contract.watch_contract(accounts(1)),
+            Err(SourceScanError::NotFound(
+                "No verified contract for bob".to_string()
+            ))
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use near_workspaces::AccountId;
 use serde_json::json;
-use verifier_contract::ContractData;
+use verifier_contract::{ContractData, Lang};
 
 #[tokio::test]
 async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,22 +25,24 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         .json()?;
     assert_eq!(&owner_result, user_account.id());
 
-    let github_data = json!({
-        "owner": "owner",
-        "repo": "repo",
-        "sha": "sha"
+    let source = json!({
+        "github": {
+            "owner": "owner",
+            "repo": "repo",
+            "sha": "0123456789abcdef0123456789abcdef01234567"
+        }
     });
     
     let set_contract_outcome = user_account
     .call(contract.id(), "set_contract")
     .args_json(json!({
         "account_id": user_account.id(),
-        "cid": "cid1",
-        "code_hash": "hash1",
+        "cid": "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34",
+        "code_hash": "11111111111111111111111111111111",
         "lang": "Rust",
-        "entry_point": "main",
+        "build_targets": [{ "crate_name": null, "entry_point": "main", "features": [] }],
         "builder_image": "rust:latest",
-        "github": github_data
+        "source": source
     }))
     .transact()
     .await?;
@@ -51,8 +53,8 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         .args_json(json!({ "account_id": user_account.id() }))
         .await?
         .json()?;
-    assert_eq!(contract_data_result.cid, "cid1");
-    assert_eq!(contract_data_result.lang, "Rust");
+    assert_eq!(contract_data_result.cid, "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34");
+    assert_eq!(contract_data_result.lang, Lang::Rust);
 
     Ok(())
 }